@@ -1 +1,7 @@
+pub mod cycle;
+pub mod interval;
+pub mod memo;
+pub mod number_theory;
+pub mod search;
 pub mod shoelace;
+pub mod shortest_path;
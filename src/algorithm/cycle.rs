@@ -0,0 +1,214 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states, each made from the one before it, starts
+/// repeating itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// How many steps it takes to reach the first state that repeats
+    pub start: usize,
+    /// How many steps it takes for a state to repeat
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Gets the earliest step that has the same state as step `n`
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle in the states made by applying `step` to `initial` over
+/// and over, using Brent's algorithm. Only a couple of states are kept at
+/// once, but `step` is called more often than with `find_with_history`.
+/// Never finishes if the states don't repeat.
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // Find the length by moving the hare on from the tortoise, teleporting
+    // the tortoise to the hare each power of two steps
+    // https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Then find the start by moving both on together, one cycle apart
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle in the states made by applying `step` to `initial` over
+/// and over, using Floyd's algorithm. Like `brent`, only a couple of states
+/// are kept at once, and it never finishes if the states don't repeat.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // Move the hare twice as fast as the tortoise until they meet
+    // https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    // The distance between them is now a multiple of the length, so moving
+    // both on together from the initial state and the meeting point finds
+    // the start
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle in the states made by applying `step` to `initial` over
+/// and over, by remembering every state until one repeats. Also gives the
+/// states up to the end of the first cycle, in order. Never finishes if the
+/// states don't repeat.
+pub fn find_with_history<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return (Cycle { start, length }, states);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Gets the state after applying `step` to `initial` `n` times. Once a state
+/// repeats, the rest of the steps are skipped over, so `n` can be far more
+/// than the number of different states.
+pub fn nth_state<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return states.swap_remove(cycle.equivalent_step(n));
+        }
+        seen.insert(state.clone(), i);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+/// Gets the state after applying `step` to `initial` `n` times, given the
+/// `cycle` those states go round, e.g. from `brent` or `floyd`
+pub fn fast_forward<S, F>(initial: S, mut step: F, n: usize, cycle: Cycle) -> S
+where
+    F: FnMut(&S) -> S,
+{
+    let mut state = initial;
+    for _ in 0..cycle.equivalent_step(n) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0 → 1 → 2 → 3 → 4 → 2 → 3 → 4 → 2 ...
+    fn step(n: &usize) -> usize {
+        [1, 2, 3, 4, 2][*n]
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 2,
+        length: 3,
+    };
+
+    #[test]
+    fn test_every_algorithm_finds_the_same_cycle() {
+        assert_eq!(brent(0, step), CYCLE);
+        assert_eq!(floyd(0, step), CYCLE);
+        let (cycle, states) = find_with_history(0, step);
+        assert_eq!(cycle, CYCLE);
+        assert_eq!(states, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_it_finds_cycles_that_start_straight_away() {
+        let step = |n: &u32| (n + 1) % 7;
+        let cycle = Cycle {
+            start: 0,
+            length: 7,
+        };
+        assert_eq!(brent(0, step), cycle);
+        assert_eq!(floyd(0, step), cycle);
+        assert_eq!(find_with_history(0, step).0, cycle);
+    }
+
+    #[test]
+    fn test_it_fast_forwards_to_a_step() {
+        assert_eq!(CYCLE.equivalent_step(1), 1);
+        assert_eq!(CYCLE.equivalent_step(10), 4);
+        for n in [0, 1, 4, 5, 10, 1_000_000_000_000] {
+            let expected = [0, 1, 2, 3, 4][CYCLE.equivalent_step(n)];
+            assert_eq!(nth_state(0, step, n), expected);
+            assert_eq!(fast_forward(0, step, n, CYCLE), expected);
+        }
+    }
+}
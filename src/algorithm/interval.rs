@@ -0,0 +1,271 @@
+use std::{fmt::Display, iter::Sum, ops::Range, ops::Sub};
+
+/// A half-open range of numbers from `start` up to but not including `end`.
+/// Unlike `Range`, it's `Copy` and can be combined with other intervals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+/// A set of numbers, held as the fewest intervals that cover them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// In order, without any that overlap or touch
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Ord,
+{
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, n: T) -> bool {
+        self.start <= n && n < self.end
+    }
+
+    /// Do the intervals have any numbers in common?
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    /// Gets the numbers that are in both intervals, if there are any
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let res = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!res.is_empty()).then_some(res)
+    }
+
+    /// Gets the numbers that are in either interval, if they make a single
+    /// interval, i.e. if the intervals overlap or touch end to end
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.start.max(other.start) > self.end.min(other.end) {
+            return None;
+        }
+        Some(Self::new(
+            self.start.min(other.start),
+            self.end.max(other.end),
+        ))
+    }
+
+    /// Gets the numbers in this interval that aren't in `other`, which can
+    /// leave up to two intervals either side of it
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        }
+        [
+            Self::new(self.start, other.start),
+            Self::new(other.end, self.end),
+        ]
+        .into_iter()
+        .filter(|i| !i.is_empty())
+        .collect()
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Ord + Sub<Output = T> + Default,
+{
+    /// Counts the numbers in the interval
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(value: Range<T>) -> Self {
+        Self {
+            start: value.start,
+            end: value.end,
+        }
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, n: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(n))
+    }
+
+    /// Gets the smallest number in the set
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// Gets the intervals in the set, in order
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// Adds every number in `interval` to the set
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Merge the new interval with every one it overlaps or touches, which
+        // all sit together in the list
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |acc, i| acc.union(i).unwrap_or(acc));
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Takes every number in `interval` out of the set
+    pub fn remove(&mut self, interval: Interval<T>) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| i.difference(&interval))
+            .collect();
+    }
+
+    /// Gets the numbers that are in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut res = self.clone();
+        for i in other.iter() {
+            res.insert(*i);
+        }
+        res
+    }
+
+    /// Gets the numbers that are in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let intervals = self
+            .iter()
+            .flat_map(|a| other.iter().filter_map(|b| a.intersection(b)))
+            .collect();
+        Self { intervals }
+    }
+
+    /// Gets the numbers in this set that aren't in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut res = self.clone();
+        for i in other.iter() {
+            res.remove(*i);
+        }
+        res
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Sub<Output = T> + Default + Sum,
+{
+    /// Counts the numbers in the set
+    pub fn len(&self) -> T {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+}
+
+impl<T> Default for IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut res = Self::new();
+        for i in iter {
+            res.insert(i);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(ranges: &[Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().map(Interval::from).collect()
+    }
+
+    #[test]
+    fn test_it_combines_intervals() {
+        let a = Interval::new(0, 10);
+        let b = Interval::new(5, 15);
+        assert_eq!(a.intersection(&b), Some(Interval::new(5, 10)));
+        assert_eq!(a.union(&b), Some(Interval::new(0, 15)));
+        assert_eq!(a.difference(&b), vec![Interval::new(0, 5)]);
+        assert_eq!(
+            a.difference(&Interval::new(3, 4)),
+            vec![Interval::new(0, 3), Interval::new(4, 10)]
+        );
+
+        let c = Interval::new(10, 12);
+        assert!(!a.overlaps(&c));
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.union(&c), Some(Interval::new(0, 12)));
+        assert_eq!(a.union(&Interval::new(11, 12)), None);
+        assert_eq!(a.len(), 10);
+        assert_eq!(Interval::new(3, 1).len(), 0);
+    }
+
+    #[test]
+    fn test_sets_merge_intervals_that_overlap_or_touch() {
+        let s = set(&[8..10, 0..2, 5..7, 1..3, 7..8]);
+        assert_eq!(s, set(&[0..3, 5..10]));
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(9));
+        assert!(!s.contains(3));
+
+        let mut s = s;
+        s.insert(Interval::new(2, 6));
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![&Interval::new(0, 10)]);
+    }
+
+    #[test]
+    fn test_it_does_set_arithmetic() {
+        let a = set(&[0..5, 10..15]);
+        let b = set(&[3..12, 20..21]);
+        assert_eq!(a.union(&b), set(&[0..15, 20..21]));
+        assert_eq!(a.intersection(&b), set(&[3..5, 10..12]));
+        assert_eq!(a.difference(&b), set(&[0..3, 12..15]));
+        assert_eq!(b.difference(&a), set(&[5..10, 20..21]));
+        assert!(a.difference(&a).is_empty());
+    }
+}
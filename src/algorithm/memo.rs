@@ -0,0 +1,82 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A function that remembers the value it gave for each key, so that it's
+/// only worked out once.
+///
+/// The function is given a `recurse` function along with the key, which it
+/// calls instead of itself, so recursive calls are remembered too. For
+/// example, `|recurse: &mut dyn FnMut(u64) -> u64, n| recurse(n - 1) + ...`.
+pub struct Memo<K, V, F> {
+    f: F,
+    cache: HashMap<K, V>,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Gets the value for `key`, working it out if it isn't known yet
+    pub fn call(&mut self, key: K) -> V {
+        Self::call_with(&self.f, &mut self.cache, key)
+    }
+
+    /// How many values are remembered
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets every value, e.g. when whatever `f` uses has changed
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    fn call_with(f: &F, cache: &mut HashMap<K, V>, key: K) -> V {
+        if let Some(value) = cache.get(&key) {
+            return value.clone();
+        }
+        let value = f(&mut |k| Self::call_with(f, cache, k), key.clone());
+        cache.insert(key, value.clone());
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_it_works_each_value_out_once() {
+        let calls = Cell::new(0);
+        let mut paths = Memo::new(|recurse: &mut dyn FnMut((u32, u32)) -> u64, (x, y)| {
+            calls.set(calls.get() + 1);
+            // The number of ways to reach (x, y) from (0, 0) moving right or down
+            if x == 0 || y == 0 {
+                1
+            } else {
+                recurse((x - 1, y)) + recurse((x, y - 1))
+            }
+        });
+        assert_eq!(paths.call((16, 16)), 601080390);
+        assert_eq!(calls.get(), 17 * 17 - 1);
+        assert_eq!(paths.len(), 17 * 17 - 1);
+
+        assert_eq!(paths.call((3, 2)), 10);
+        assert_eq!(calls.get(), 17 * 17 - 1);
+        paths.clear();
+        assert!(paths.is_empty());
+    }
+}
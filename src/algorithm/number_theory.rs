@@ -0,0 +1,199 @@
+use num::{CheckedMul, CheckedSub, Integer};
+
+/// Gets the greatest common divisor of `a` and `b`, which is never negative.
+/// The gcd of 0 and 0 is 0. The gcd of `T::MIN` and 0 or `T::MIN` doesn't fit
+/// in a signed `T`, so it overflows.
+pub fn gcd<T>(a: T, b: T) -> T
+where
+    T: Integer + Copy,
+{
+    let (mut a, mut b) = (a, b);
+    while b != T::zero() {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// Gets the lowest common multiple of `a` and `b`, which is never negative,
+/// or `None` if it's too big for `T`. The lcm of 0 and anything is 0.
+pub fn checked_lcm<T>(a: T, b: T) -> Option<T>
+where
+    T: Integer + CheckedMul + CheckedSub + Copy,
+{
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+    let (a, b) = (checked_abs(a)?, checked_abs(b)?);
+    (a / gcd(a, b)).checked_mul(&b)
+}
+
+/// Gets the lowest common multiple of every number in `ns`, or `None` if
+/// it's too big for `T`. The lcm of no numbers is 1.
+pub fn checked_lcm_all<T>(ns: impl IntoIterator<Item = T>) -> Option<T>
+where
+    T: Integer + CheckedMul + CheckedSub + Copy,
+{
+    ns.into_iter()
+        .try_fold(T::one(), |acc, n| checked_lcm(acc, n))
+}
+
+/// Finds the gcd `g` of `a` and `b`, along with `x` and `y` such that
+/// `a * x + b * y == g`, using the extended Euclidean algorithm. Gives `None`
+/// if `g` is too big for an `i64`, which is when it's the gcd of `i64::MIN`
+/// and 0 or `i64::MIN`.
+pub fn extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    // https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    Some((
+        i64::try_from(old_r).ok()?,
+        i64::try_from(old_x).ok()?,
+        i64::try_from(old_y).ok()?,
+    ))
+}
+
+/// Finds `x` in `0..m` such that `a * x` is 1 modulo `m`, if there is one,
+/// which is when `a` and `m` are coprime. Gives `None` if `m` is 0 or
+/// `i64::MIN`.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let m = m.checked_abs().filter(|m| *m != 0)?;
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m)?;
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Calculates `base` to the power of `exp`, modulo `m`. Panics if `m` is 0.
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+    assert!(m > 0, "the modulus must be positive");
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut exp = exp;
+    let mut res = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    res as u64
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)`, given as
+/// `(residue, modulus)` pairs, using the Chinese remainder theorem. The
+/// moduli don't have to be coprime. Gives `(x, m)` where every solution is
+/// `x` plus a multiple of `m`, and `x` is in `0..m`, or `None` if there's no
+/// solution, a modulus is 0, or `m` is too big for an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences.iter().try_fold((0, 1), |(a1, m1), &(a2, m2)| {
+        combine(a1, m1, a2, m2.checked_abs()?)
+    })
+}
+
+/// Combines `x ≡ a1 (mod m1)` and `x ≡ a2 (mod m2)` into one congruence
+fn combine(a1: i64, m1: i64, a2: i64, m2: i64) -> Option<(i64, i64)> {
+    if m2 == 0 {
+        return None;
+    }
+    let (g, p, _) = extended_gcd(m1, m2)?;
+    let diff = a2 as i128 - a1 as i128;
+    if diff % g as i128 != 0 {
+        // The congruences disagree about x modulo g
+        return None;
+    }
+    let lcm = i64::try_from(m1 as i128 / g as i128 * m2 as i128).ok()?;
+    // m1 * p ≡ g (mod m2), so adding m1 * p * diff / g to a1 fixes x modulo m2
+    // without changing it modulo m1
+    let step = (diff / g as i128 * p as i128).rem_euclid(m2 as i128 / g as i128);
+    let x = (a1 as i128 + m1 as i128 * step).rem_euclid(lcm as i128);
+    Some((x as i64, lcm))
+}
+
+fn abs<T>(n: T) -> T
+where
+    T: Integer + Copy,
+{
+    if n < T::zero() {
+        T::zero() - n
+    } else {
+        n
+    }
+}
+
+fn checked_abs<T>(n: T) -> Option<T>
+where
+    T: Integer + CheckedSub + Copy,
+{
+    if n < T::zero() {
+        T::zero().checked_sub(&n)
+    } else {
+        Some(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_it_finds_gcds_and_lcms() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 5), 5);
+        assert_eq!(checked_lcm(4u32, 6), Some(12));
+        assert_eq!(checked_lcm(-4i32, 6), Some(12));
+        assert_eq!(checked_lcm(0u64, 6), Some(0));
+        assert_eq!(checked_lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(checked_lcm_all([u64::MAX, u64::MAX - 1]), None);
+        assert_eq!(checked_lcm(i32::MIN, 1), None);
+        assert_eq!(checked_lcm(i32::MIN, 2), None);
+    }
+
+    #[test]
+    fn test_it_finds_bezout_coefficients() {
+        for (a, b) in [(240, 46), (46, 240), (-7, 3), (0, 5), (17, 0)] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(extended_gcd(0, 0), Some((0, 1, 0)));
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), None);
+        assert_eq!(extended_gcd(i64::MIN, -1), Some((1, 0, -1)));
+    }
+
+    #[test]
+    fn test_it_does_modular_arithmetic() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, i64::MIN), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn test_it_solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // The moduli don't have to be coprime
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[(2, 3), (1, 0)]), None);
+        assert_eq!(crt(&[(0, i64::MIN)]), None);
+    }
+}
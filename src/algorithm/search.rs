@@ -0,0 +1,245 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use crate::grid::Grid;
+
+/// Finds every node that can be reached from any of `starts`, along with the
+/// fewest steps it takes to reach it, using a breadth first search
+pub fn bfs<N, I, FN>(starts: impl IntoIterator<Item = N>, neighbours: FN) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+{
+    bfs_layers(starts, neighbours, |_, _| {})
+}
+
+/// Like `bfs`, but also calls `on_layer` with each distance from the starts,
+/// in order, and every node at that distance
+pub fn bfs_layers<N, I, FN, FL>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+    mut on_layer: FL,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+    FL: FnMut(usize, &[N]),
+{
+    let mut dists = HashMap::new();
+    let mut layer = vec![];
+    for start in starts {
+        if !dists.contains_key(&start) {
+            dists.insert(start.clone(), 0);
+            layer.push(start);
+        }
+    }
+
+    let mut dist = 0;
+    while !layer.is_empty() {
+        on_layer(dist, &layer);
+        dist += 1;
+        let mut next_layer = vec![];
+        for node in layer.iter() {
+            for next in neighbours(node) {
+                if !dists.contains_key(&next) {
+                    dists.insert(next.clone(), dist);
+                    next_layer.push(next);
+                }
+            }
+        }
+        layer = next_layer;
+    }
+    dists
+}
+
+/// Finds every node that can be reached from `start` using a depth first
+/// search, in the order they're first visited
+pub fn dfs<N, I, FN>(start: N, mut neighbours: FN) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+{
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut to_visit = vec![start];
+    while let Some(node) = to_visit.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        to_visit.extend(
+            neighbours(&node)
+                .into_iter()
+                .filter(|n| !visited.contains(n)),
+        );
+        order.push(node);
+    }
+    order
+}
+
+/// Finds every cell of `grid` that can be reached from `start` by moving
+/// north, east, south or west through cells that `is_open` accepts. Nothing
+/// is reached if `start` isn't open itself.
+pub fn flood_fill<T, F>(
+    grid: &Grid<T>,
+    start: (usize, usize),
+    mut is_open: F,
+) -> HashSet<(usize, usize)>
+where
+    F: FnMut(&T) -> bool,
+{
+    if !grid.get(start).is_some_and(&mut is_open) {
+        return HashSet::new();
+    }
+    bfs([start], |&coord| {
+        grid.neighbours4(coord)
+            .filter(|n| is_open(&grid[*n]))
+            .collect::<Vec<_>>()
+    })
+    .into_keys()
+    .collect()
+}
+
+/// Splits `nodes` into groups that are connected to each other, where
+/// `neighbours` must go both ways. Groups are in the order of their first
+/// node in `nodes`.
+pub fn connected_components<N, I, FN>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = dfs(node, &mut neighbours);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+    components
+}
+
+/// Orders `nodes` so that each one comes before all of its `successors`,
+/// using Kahn's algorithm. Nodes that aren't constrained keep their order
+/// from `nodes`, and repeats of a node are dropped. Successors that aren't in
+/// `nodes` are ignored. Gives `None` if the successors form a cycle, so
+/// there's no such order.
+pub fn topological_sort<N, I, FS>(nodes: &[N], mut successors: FS) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> I,
+{
+    // https://en.wikipedia.org/wiki/Topological_sorting#Kahn's_algorithm
+    let mut known = HashSet::new();
+    let nodes = nodes
+        .iter()
+        .filter(|n| known.insert(*n))
+        .collect::<Vec<_>>();
+    let edges = nodes
+        .iter()
+        .map(|n| {
+            let nexts = successors(n)
+                .into_iter()
+                .filter(|next| known.contains(next))
+                .collect::<Vec<_>>();
+            ((*n).clone(), nexts)
+        })
+        .collect::<HashMap<_, _>>();
+    let mut predecessor_counts = nodes
+        .iter()
+        .map(|n| ((*n).clone(), 0))
+        .collect::<HashMap<_, usize>>();
+    for next in edges.values().flatten() {
+        *predecessor_counts.get_mut(next).unwrap() += 1;
+    }
+
+    let mut to_visit = nodes
+        .iter()
+        .filter(|n| predecessor_counts[**n] == 0)
+        .map(|n| (*n).clone())
+        .collect::<VecDeque<_>>();
+    let mut order = vec![];
+    while let Some(node) = to_visit.pop_front() {
+        for next in edges.get(&node).into_iter().flatten() {
+            let count = predecessor_counts.get_mut(next).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                to_visit.push_back(next.clone());
+            }
+        }
+        order.push(node);
+    }
+    (order.len() == predecessor_counts.len()).then_some(order)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0 → 1 → 2 → 3, and 0 → 4 → 3, with 5 on its own
+    fn successors(n: &u32) -> Vec<u32> {
+        match n {
+            0 => vec![1, 4],
+            1 => vec![2],
+            2 | 4 => vec![3],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs_finds_distances_layer_by_layer() {
+        let mut layers = vec![];
+        let dists = bfs_layers([0], successors, |d, layer| layers.push((d, layer.to_vec())));
+        assert_eq!(
+            dists,
+            HashMap::from([(0, 0), (1, 1), (4, 1), (2, 2), (3, 2)])
+        );
+        assert_eq!(layers, vec![(0, vec![0]), (1, vec![1, 4]), (2, vec![2, 3])]);
+        assert_eq!(bfs([5], successors), HashMap::from([(5, 0)]));
+    }
+
+    #[test]
+    fn test_dfs_goes_as_deep_as_it_can_first() {
+        assert_eq!(dfs(0, successors), vec![0, 4, 3, 1, 2]);
+        assert_eq!(dfs(2, successors), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_it_finds_connected_areas() {
+        let grid = Grid::parse("..#.\n.##.\n#...", |c| Some(c == '.'), "").unwrap();
+        let filled = flood_fill(&grid, (0, 0), |open| *open);
+        assert_eq!(filled, HashSet::from([(0, 0), (1, 0), (0, 1)]));
+        assert_eq!(flood_fill(&grid, (3, 0), |open| *open).len(), 5);
+        assert!(flood_fill(&grid, (2, 0), |open| *open).is_empty());
+
+        let open = grid.coords().filter(|c| grid[*c]).collect::<Vec<_>>();
+        let components = connected_components(open, |&c| {
+            grid.neighbours4(c).filter(|n| grid[*n]).collect::<Vec<_>>()
+        });
+        let sizes = components.iter().map(Vec::len).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![3, 5]);
+    }
+
+    #[test]
+    fn test_it_sorts_topologically() {
+        let order = topological_sort(&[3, 2, 1, 0, 4, 5], successors).unwrap();
+        assert_eq!(order, vec![0, 5, 1, 4, 2, 3]);
+        assert_eq!(topological_sort(&[1, 2], |n| vec![3 - n]), None);
+        // Repeats aren't a cycle
+        let order = topological_sort(&[2, 0, 2, 1, 0], successors).unwrap();
+        assert_eq!(order, vec![0, 1, 2]);
+        // Successors from outside `nodes` aren't included
+        assert_eq!(topological_sort(&[2, 0], successors), Some(vec![2, 0]));
+    }
+}
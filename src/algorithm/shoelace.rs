@@ -4,6 +4,17 @@ use crate::point::Point;
 use itertools::Itertools;
 use num::{FromPrimitive, Integer, ToPrimitive};
 
+/// How many integer points a polygon with integer vertices covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatticeCount<I> {
+    /// The points on the polygon's perimeter
+    pub boundary: I,
+    /// The points strictly inside the polygon
+    pub interior: I,
+    /// Every point on or inside the polygon
+    pub total: I,
+}
+
 /// Calculates the area enclosed by the loop defined by `loop_points`.
 /// Requires that`loop_points` contains all integer points on the
 /// loop's perimeter.
@@ -13,26 +24,7 @@ where
     I: Copy + Integer + FromPrimitive + ToPrimitive + Sum,
 {
     let two = FromPrimitive::from_i32(2).unwrap();
-    // Calculate the area enclosed by the loop
-    // https://en.wikipedia.org/wiki/Shoelace_formula
-    let mut area = loop_points
-        .iter()
-        .tuple_windows()
-        .map(|(a, b)| {
-            let x1 = a.x();
-            let y1 = a.y();
-            let x2 = b.x();
-            let y2 = b.y();
-            let p1 = x1 * y2;
-            let p2 = y1 * x2;
-            p1 - p2
-        })
-        .sum::<I>()
-        / two;
-    if area < I::zero() {
-        let minus_one = FromPrimitive::from_i32(-1).unwrap();
-        area = area * minus_one;
-    }
+    let area = double_area(loop_points) / two;
 
     // Then calculate the number of points enclosed by the loop
     // loopArea - (boundaryPointsCount / 2) + 1
@@ -41,6 +33,50 @@ where
     area - (boundary_points_count / two) + I::one()
 }
 
+/// Counts the integer points covered by the polygon with the given
+/// `vertices`, in order, with `perimeter` integer points on its edges. Unlike
+/// `enclosed_area`, only the corners are needed, so edges can be any length.
+/// The polygon is closed whether or not the first vertex is repeated at the
+/// end.
+pub fn lattice_count<V, I>(vertices: &[V], perimeter: I) -> LatticeCount<I>
+where
+    V: Sized + Point<I>,
+    I: Copy + Integer + FromPrimitive + ToPrimitive + Sum,
+{
+    let two: I = FromPrimitive::from_i32(2).unwrap();
+    // Pick's theorem says area = interior + boundary / 2 - 1, and working
+    // with twice the area keeps it whole
+    // https://en.wikipedia.org/wiki/Pick's_theorem
+    let interior = (double_area(vertices) - perimeter + two) / two;
+    LatticeCount {
+        boundary: perimeter,
+        interior,
+        total: interior + perimeter,
+    }
+}
+
+/// Calculates twice the area of the polygon with the given `vertices`, which
+/// is a whole number when they are
+fn double_area<V, I>(vertices: &[V]) -> I
+where
+    V: Sized + Point<I>,
+    I: Copy + Integer + FromPrimitive + ToPrimitive + Sum,
+{
+    // https://en.wikipedia.org/wiki/Shoelace_formula
+    // Repeating the first vertex at the end adds nothing to the sum, so the
+    // loop can always be closed
+    let area = vertices
+        .iter()
+        .circular_tuple_windows()
+        .map(|(a, b)| a.x() * b.y() - a.y() * b.x())
+        .sum::<I>();
+    if area < I::zero() {
+        I::zero() - area
+    } else {
+        area
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -76,4 +112,29 @@ mod test {
         ];
         assert_eq!(enclosed_area(&points), 1);
     }
+
+    #[test]
+    fn test_it_counts_points_from_the_vertices() {
+        // A 3x3 square, open and closed
+        let corners = vec![Coord(0, 0), Coord(2, 0), Coord(2, 2), Coord(0, 2)];
+        let expected = LatticeCount {
+            boundary: 8,
+            interior: 1,
+            total: 9,
+        };
+        assert_eq!(lattice_count(&corners, 8), expected);
+        let closed = vec![
+            Coord(0, 0),
+            Coord(0, 2),
+            Coord(2, 2),
+            Coord(2, 0),
+            Coord(0, 0),
+        ];
+        assert_eq!(lattice_count(&closed, 8), expected);
+
+        // A right-angled triangle with a diagonal edge that only meets
+        // the points at its ends
+        let triangle = vec![Coord(0, 0), Coord(4, 0), Coord(0, 3)];
+        assert_eq!(lattice_count(&triangle, 8).interior, 3);
+    }
 }
@@ -0,0 +1,390 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use num::Zero;
+
+/// The cheapest path found to a destination.
+#[derive(Debug, Clone)]
+pub struct ShortestPath<N, C> {
+    pub cost: C,
+    /// From the start to the destination, including both
+    pub path: Vec<N>,
+    /// For each node that was reached, the node it was reached from most
+    /// cheaply. Starts have no predecessor.
+    pub predecessors: HashMap<N, N>,
+}
+
+/// Every one of the cheapest paths found to the nearest destinations.
+#[derive(Debug, Clone)]
+pub struct AllShortestPaths<N, C> {
+    pub cost: C,
+    /// Every destination that can be reached for `cost`
+    pub destinations: Vec<N>,
+    /// For each node that was reached, every node it can be reached from
+    /// most cheaply. Starts have no predecessors.
+    pub predecessors: HashMap<N, Vec<N>>,
+}
+
+/// A node waiting to be visited, ordered so that the one with the lowest
+/// priority is visited first.
+struct Visit<N, C> {
+    node: N,
+    cost: C,
+    priority: C,
+}
+
+/// Finds the cheapest path from any of `starts` to a node that `is_dest`
+/// accepts, using Dijkstra's algorithm. `cost` gives the cost of moving
+/// between two neighbouring nodes, which mustn't be negative.
+pub fn dijkstra<N, C, I, FN, FC, FD>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: FN,
+    cost: FC,
+    is_dest: FD,
+) -> Option<ShortestPath<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+    FC: FnMut(&N, &N) -> C,
+    FD: FnMut(&N) -> bool,
+{
+    a_star(starts, neighbours, cost, |_| C::zero(), is_dest)
+}
+
+/// Finds the cheapest path from any of `starts` to a node that `is_dest`
+/// accepts, using A*. `heuristic` estimates the cost from a node to the
+/// nearest destination, and the path is only guaranteed to be the cheapest
+/// if it never overestimates.
+pub fn a_star<N, C, I, FN, FC, FH, FD>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+    mut cost: FC,
+    mut heuristic: FH,
+    mut is_dest: FD,
+) -> Option<ShortestPath<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+    FC: FnMut(&N, &N) -> C,
+    FH: FnMut(&N) -> C,
+    FD: FnMut(&N) -> bool,
+{
+    // The cheapest cost found to each node, and the node it was reached from
+    let mut best: HashMap<N, (C, Option<N>)> = HashMap::new();
+    let mut to_visit = BinaryHeap::new();
+
+    for start in starts {
+        best.insert(start.clone(), (C::zero(), None));
+        let priority = heuristic(&start);
+        to_visit.push(Reverse(Visit {
+            node: start,
+            cost: C::zero(),
+            priority,
+        }));
+    }
+
+    while let Some(Reverse(Visit {
+        node, cost: dist, ..
+    })) = to_visit.pop()
+    {
+        // A cheaper way to this node has been found since this visit was
+        // queued. Nodes aren't closed once visited, as a heuristic that
+        // doesn't overestimate can still lead to them too expensively first.
+        if best
+            .get(&node)
+            .is_some_and(|(cheapest, _)| dist > *cheapest)
+        {
+            continue;
+        }
+
+        if is_dest(&node) {
+            let predecessors = best
+                .into_iter()
+                .filter_map(|(node, (_, prev))| Some((node, prev?)))
+                .collect();
+            let path = reconstruct_path(&predecessors, node);
+            return Some(ShortestPath {
+                cost: dist,
+                path,
+                predecessors,
+            });
+        }
+
+        for next in neighbours(&node) {
+            let new_cost = dist + cost(&node, &next);
+            let is_cheaper = best.get(&next).is_none_or(|(prev, _)| new_cost < *prev);
+            if is_cheaper {
+                best.insert(next.clone(), (new_cost, Some(node.clone())));
+                let priority = new_cost + heuristic(&next);
+                to_visit.push(Reverse(Visit {
+                    node: next,
+                    cost: new_cost,
+                    priority,
+                }));
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds every cheapest path from any of `starts` to the nearest nodes that
+/// `is_dest` accepts, using Dijkstra's algorithm. Unlike with `dijkstra`,
+/// `cost` must always be positive, as paths could go round in circles
+/// otherwise.
+pub fn all_shortest_paths<N, C, I, FN, FC, FD>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: FN,
+    mut cost: FC,
+    mut is_dest: FD,
+) -> Option<AllShortestPaths<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> I,
+    FC: FnMut(&N, &N) -> C,
+    FD: FnMut(&N) -> bool,
+{
+    let mut costs = HashMap::new();
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut to_visit = BinaryHeap::new();
+    let mut visited = HashSet::new();
+    let mut found: Option<(C, Vec<N>)> = None;
+
+    for start in starts {
+        costs.insert(start.clone(), C::zero());
+        to_visit.push(Reverse(Visit {
+            node: start,
+            cost: C::zero(),
+            priority: C::zero(),
+        }));
+    }
+
+    while let Some(Reverse(Visit {
+        node, cost: dist, ..
+    })) = to_visit.pop()
+    {
+        if found.as_ref().is_some_and(|(best, _)| dist > *best) {
+            // Every destination as close as the nearest one has been found
+            break;
+        }
+        if visited.contains(&node) {
+            continue;
+        }
+        visited.insert(node.clone());
+
+        if is_dest(&node) {
+            found.get_or_insert_with(|| (dist, vec![])).1.push(node);
+            continue;
+        }
+
+        for next in neighbours(&node) {
+            let new_cost = dist + cost(&node, &next);
+            match costs.get(&next).map(|prev| new_cost.cmp(prev)) {
+                Some(Ordering::Greater) => {}
+                Some(Ordering::Equal) => {
+                    predecessors.entry(next).or_default().push(node.clone());
+                }
+                Some(Ordering::Less) | None => {
+                    costs.insert(next.clone(), new_cost);
+                    predecessors.insert(next.clone(), vec![node.clone()]);
+                    to_visit.push(Reverse(Visit {
+                        node: next,
+                        cost: new_cost,
+                        priority: new_cost,
+                    }));
+                }
+            }
+        }
+    }
+
+    found.map(|(cost, destinations)| AllShortestPaths {
+        cost,
+        destinations,
+        predecessors,
+    })
+}
+
+/// Follows `predecessors` back from `end` to build the path that reached it
+pub fn reconstruct_path<N>(predecessors: &HashMap<N, N>, end: N) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut path = vec![end];
+    while let Some(prev) = predecessors.get(&path[path.len() - 1]) {
+        path.push(prev.clone());
+    }
+    path.reverse();
+    path
+}
+
+impl<N, C> AllShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+{
+    /// Gets every node that's on at least one of the paths
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut to_visit = self.destinations.clone();
+        while let Some(node) = to_visit.pop() {
+            if let Some(prevs) = self.predecessors.get(&node) {
+                to_visit.extend(prevs.iter().filter(|p| !nodes.contains(*p)).cloned());
+            }
+            nodes.insert(node);
+        }
+        nodes
+    }
+
+    /// Lists every path from a start to a destination. There can be very
+    /// many of them, so `nodes` is better when the paths themselves aren't
+    /// needed.
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut paths = vec![];
+        for dest in self.destinations.iter() {
+            self.extend_paths(vec![dest.clone()], &mut paths);
+        }
+        paths
+    }
+
+    /// Adds every path that ends with `tail` (which is in reverse) to `paths`
+    fn extend_paths(&self, tail: Vec<N>, paths: &mut Vec<Vec<N>>) {
+        match self.predecessors.get(&tail[tail.len() - 1]) {
+            None => paths.push(tail.into_iter().rev().collect()),
+            Some(prevs) => {
+                for prev in prevs {
+                    let mut tail = tail.clone();
+                    tail.push(prev.clone());
+                    self.extend_paths(tail, paths);
+                }
+            }
+        }
+    }
+}
+
+impl<N, C: Ord> Ord for Visit<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Visit<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Visit<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Visit<N, C> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Neighbours on a `size` by `size` grid, without diagonals
+    fn grid_neighbours(size: i32) -> impl Fn(&(i32, i32)) -> Vec<(i32, i32)> {
+        move |&(x, y)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..size).contains(x) && (0..size).contains(y))
+                .collect()
+        }
+    }
+
+    /// Moving into a cell in column 1 costs 10, anything else costs 1
+    fn wall_cost(_: &(i32, i32), to: &(i32, i32)) -> u32 {
+        if to.0 == 1 {
+            10
+        } else {
+            1
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_finds_the_cheapest_path() {
+        let res = dijkstra([(0, 0)], grid_neighbours(3), wall_cost, |n| *n == (2, 0)).unwrap();
+        // Column 1 has to be crossed somewhere, so it's cheapest to go straight
+        // across
+        assert_eq!(res.cost, 11);
+        assert_eq!(res.path, vec![(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(res.path.first(), Some(&(0, 0)));
+        assert_eq!(res.path.last(), Some(&(2, 0)));
+        assert_eq!(reconstruct_path(&res.predecessors, (2, 0)), res.path);
+        assert!(!res.predecessors.contains_key(&(0, 0)));
+    }
+
+    #[test]
+    fn test_a_star_agrees_with_dijkstra() {
+        let dest = (4, 4);
+        let manhattan = |n: &(i32, i32)| ((dest.0 - n.0).abs() + (dest.1 - n.1).abs()) as u32;
+        let res = a_star([(0, 0)], grid_neighbours(5), wall_cost, manhattan, |n| {
+            *n == dest
+        })
+        .unwrap();
+        let expected = dijkstra([(0, 0)], grid_neighbours(5), wall_cost, |n| *n == dest).unwrap();
+        assert_eq!(res.cost, expected.cost);
+        assert_eq!(res.path.len(), 9);
+    }
+
+    #[test]
+    fn test_a_star_revisits_nodes_when_a_cheaper_way_turns_up() {
+        // The heuristic never overestimates, but it leads to C the expensive
+        // way round first
+        let neighbours = |n: &char| match n {
+            'S' => vec!['A', 'B'],
+            'A' | 'B' => vec!['C'],
+            'C' => vec!['G'],
+            _ => vec![],
+        };
+        let cost = |from: &char, to: &char| match (from, to) {
+            ('B', 'C') => 3,
+            ('C', 'G') => 10,
+            _ => 1,
+        };
+        let heuristic = |n: &char| if *n == 'A' { 11 } else { 0 };
+        let res = a_star(['S'], neighbours, cost, heuristic, |n| *n == 'G').unwrap();
+        assert_eq!(res.cost, 12);
+        assert_eq!(res.path, vec!['S', 'A', 'C', 'G']);
+    }
+
+    #[test]
+    fn test_it_gives_up_when_there_is_no_path() {
+        let res = dijkstra([(0, 0)], grid_neighbours(3), wall_cost, |n| *n == (5, 5));
+        assert!(res.is_none());
+    }
+
+    #[test]
+    fn test_it_finds_all_shortest_paths() {
+        let res =
+            all_shortest_paths([(0, 0)], grid_neighbours(3), |_, _| 1, |n| *n == (2, 2)).unwrap();
+        assert_eq!(res.cost, 4);
+        assert_eq!(res.destinations, vec![(2, 2)]);
+        assert_eq!(res.paths().len(), 6);
+        assert_eq!(res.nodes().len(), 9);
+
+        // Only the paths along the edges avoid the expensive middle
+        let res = all_shortest_paths(
+            [(0, 0)],
+            grid_neighbours(3),
+            |_, to| if *to == (1, 1) { 5 } else { 1 },
+            |n| *n == (2, 2),
+        )
+        .unwrap();
+        assert_eq!(res.paths().len(), 2);
+        assert!(!res.nodes().contains(&(1, 1)));
+    }
+}
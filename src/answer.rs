@@ -0,0 +1,163 @@
+use num::{BigInt, BigUint};
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+
+/// The answer to a puzzle.
+///
+/// Each day's solver returns whichever type suits it, which is converted into
+/// an `Answer` with `From`. The kind of integer is kept, but answers are
+/// compared by value, so e.g. `Answer::from(5usize) == Answer::from(5i32)`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    /// An integer that doesn't fit in 64 bits
+    Big(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    /// A short name for the kind of answer, e.g. "unsigned"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Unsigned(_) => "unsigned",
+            Self::Signed(_) => "signed",
+            Self::Big(_) => "big",
+            Self::Text(_) => "text",
+        }
+    }
+
+    /// The answer's value if it's an integer
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Self::Unsigned(n) => Some(BigInt::from(*n)),
+            Self::Signed(n) => Some(BigInt::from(*n)),
+            Self::Big(n) => Some(n.clone()),
+            Self::Text(_) => None,
+        }
+    }
+}
+
+/// Integers are compared by value. Text is compared with the other answer as
+/// it would be displayed, so "42" is equal to 42.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.to_bigint(), other.to_bigint()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned(n) => write!(f, "{}", n),
+            Self::Signed(n) => write!(f, "{}", n),
+            Self::Big(n) => write!(f, "{}", n),
+            Self::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// 64-bit integers are written as numbers. Big integers are written as
+/// strings, since many readers can't handle numbers that large.
+impl Serialize for Answer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Unsigned(n) => serializer.serialize_u64(*n),
+            Self::Signed(n) => serializer.serialize_i64(*n),
+            Self::Big(n) => serializer.collect_str(n),
+            Self::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $as:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::$variant(n as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(Unsigned, u64, u8, u16, u32, u64, usize);
+impl_from_int!(Signed, i64, i8, i16, i32, i64, isize);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        u64::try_from(n).map_or_else(|_| Self::Big(BigInt::from(n)), Self::Unsigned)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or_else(|_| Self::Big(BigInt::from(n)), Self::Signed)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Self::Big(n)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        Self::Big(BigInt::from(n))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_it_keeps_the_kind_of_integer() {
+        assert_eq!(Answer::from(3usize).kind(), "unsigned");
+        assert_eq!(Answer::from(3i32).kind(), "signed");
+        assert_eq!(Answer::from(u128::MAX).kind(), "big");
+        assert_eq!(Answer::from(3u128).kind(), "unsigned");
+        assert_eq!(Answer::from("abc").kind(), "text");
+    }
+
+    #[test]
+    fn test_it_compares_answers_by_value() {
+        assert_eq!(Answer::from(5usize), Answer::from(5i64));
+        assert_eq!(Answer::from(5u8), Answer::from(BigInt::from(5)));
+        assert_eq!(Answer::from(-4i32), Answer::from("-4"));
+        assert_ne!(Answer::from(-4i32), Answer::from(4u32));
+        assert_ne!(Answer::from("abc"), Answer::from("abd"));
+    }
+
+    #[test]
+    fn test_it_displays_answers() {
+        assert_eq!(Answer::from(-12i64).to_string(), "-12");
+        assert_eq!(
+            Answer::from(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(Answer::from("HO-HO-HO").to_string(), "HO-HO-HO");
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{anyhow, bail, Context, Result};
+use std::{collections::HashMap, fmt::Display, path::Path};
+
+use crate::answer::Answer;
+use crate::solver::registry;
+
+/// Known-good answers, loaded from a TOML file in which each year is a table
+/// of days, and each day is a table of parts, e.g.
+///
+/// ```toml
+/// [2023.day1]
+/// part1 = 142
+/// part2 = 281
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u16, u16, u16), Answer>,
+}
+
+/// How a puzzle's answer compares with the stored answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+    /// There's no stored answer to compare with
+    Unknown,
+}
+
+impl Answers {
+    /// Loads answers from the file at `path`. A missing file is treated as
+    /// having no stored answers.
+    pub fn load<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path).context("could not read answers file")?;
+        Self::parse(&contents).with_context(|| format!("invalid answers file {}", path.display()))
+    }
+
+    /// The expected answers of every registered example
+    pub fn examples() -> Self {
+        let answers = registry()
+            .examples()
+            .map(|e| ((e.year, e.day, e.part), e.expected.clone()))
+            .collect();
+        Self { answers }
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let table = input.parse::<toml::Table>()?;
+        let mut answers = HashMap::new();
+        for (year_key, days) in table.iter() {
+            let year = parse_key(year_key, "")?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| anyhow!("'{}' must be a table of days", year_key))?;
+            for (day_key, parts) in days.iter() {
+                let day = parse_key(day_key, "day")?;
+                let parts = parts.as_table().ok_or_else(|| {
+                    anyhow!("'{}.{}' must be a table of parts", year_key, day_key)
+                })?;
+                for (part_key, value) in parts.iter() {
+                    let part = parse_key(part_key, "part")?;
+                    let answer = match value {
+                        toml::Value::Integer(n) => Answer::from(*n),
+                        toml::Value::String(s) => Answer::from(s.as_str()),
+                        _ => bail!(
+                            "{}.{}.{} must be an integer or a string",
+                            year_key,
+                            day_key,
+                            part_key
+                        ),
+                    };
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, year: u16, day: u16, part: u16) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    /// Compares `answer` with the stored answer for the puzzle
+    pub fn check(&self, year: u16, day: u16, part: u16, answer: &Answer) -> Verdict {
+        match self.get(year, day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/// Parses keys such as "day12" or "part2", or "2023" when `prefix` is empty
+fn parse_key(key: &str, prefix: &str) -> Result<u16> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse::<u16>().ok())
+        .ok_or_else(|| anyhow!("expected a key like '{}1' but got '{}'", prefix, key))
+}
+
+impl Verdict {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Self::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = r#"
+[2023.day1]
+part1 = 142
+part2 = "281"
+
+[2023.day10]
+part2 = -4
+
+[2022.day1]
+part1 = 24000
+"#;
+
+    #[test]
+    fn test_can_parse_answers() {
+        let answers = Answers::parse(INPUT).unwrap();
+        assert_eq!(answers.get(2023, 1, 1), Some(&Answer::from(142)));
+        assert_eq!(answers.get(2023, 1, 2), Some(&Answer::from("281")));
+        assert_eq!(answers.get(2023, 10, 1), None);
+        assert_eq!(answers.get(2023, 10, 2), Some(&Answer::from(-4)));
+        assert_eq!(answers.get(2022, 1, 1), Some(&Answer::from(24000)));
+        assert_eq!(answers.get(2022, 1, 2), None);
+    }
+
+    #[test]
+    fn test_rejects_badly_named_keys() {
+        assert!(Answers::parse("[2023.one]\npart1 = 1").is_err());
+        assert!(Answers::parse("[2023.day1]\nfirst = 1").is_err());
+        assert!(Answers::parse("[2023.day1]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("[day1]\npart1 = 1").is_err());
+    }
+
+    #[test]
+    fn test_can_check_an_answer() {
+        let answers = Answers::parse(INPUT).unwrap();
+        assert_eq!(answers.check(2023, 1, 1, &142usize.into()), Verdict::Pass);
+        assert_eq!(answers.check(2023, 1, 2, &281usize.into()), Verdict::Pass);
+        assert_eq!(answers.check(2023, 10, 2, &(-4i32).into()), Verdict::Pass);
+        assert_eq!(
+            answers.check(2023, 1, 2, &280usize.into()),
+            Verdict::Fail {
+                expected: Answer::from("281")
+            }
+        );
+        assert_eq!(answers.check(2023, 2, 1, &1usize.into()), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_has_the_answers_to_the_examples() {
+        let answers = Answers::examples();
+        assert_eq!(answers.check(2023, 1, 1, &142usize.into()), Verdict::Pass);
+        assert_eq!(answers.check(2023, 1, 2, &281usize.into()), Verdict::Pass);
+    }
+}
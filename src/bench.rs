@@ -0,0 +1,172 @@
+use anyhow::{ensure, Result};
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::input::Loader;
+use crate::puzzle::Puzzle;
+
+/// Summary statistics for a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+/// Timings from running a puzzle repeatedly.
+pub struct BenchResult {
+    pub puzzle: Puzzle,
+    pub warmup: usize,
+    pub runs: usize,
+    /// Time taken to read the puzzle's input
+    pub load: Stats,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl Stats {
+    /// Calculates the statistics for `samples`, or `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        // nearest-rank percentile
+        let p95 = sorted[(n * 95).div_ceil(100) - 1];
+        let variance = sorted
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(Self {
+            min: sorted[0],
+            mean,
+            median,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Runs `puzzle` `warmup` times, discarding the timings, then another `runs`
+/// times to collect timings. Stops at the first error the puzzle returns.
+/// There must be at least one timed run.
+pub fn bench<L>(puzzle: &Puzzle, loader: &L, warmup: usize, runs: usize) -> Result<BenchResult>
+where
+    L: Loader + ?Sized,
+{
+    ensure!(runs > 0, "there must be at least one timed run");
+    let solver = puzzle.solver()?;
+    let mut load_samples = Vec::with_capacity(runs);
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+
+    for i in 0..(warmup + runs) {
+        let start = Instant::now();
+        let input = puzzle.load_input(loader)?;
+        let load = start.elapsed();
+
+        let start = Instant::now();
+        let parsed = solver.parse(&input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        solver.solve(&parsed)?;
+        let solve = start.elapsed();
+
+        if i >= warmup {
+            load_samples.push(load);
+            parse_samples.push(parse);
+            solve_samples.push(solve);
+        }
+    }
+
+    Ok(BenchResult {
+        puzzle: puzzle.clone(),
+        warmup,
+        runs: load_samples.len(),
+        load: Stats::from_samples(&load_samples).unwrap(),
+        parse: Stats::from_samples(&parse_samples).unwrap(),
+        solve: Stats::from_samples(&solve_samples).unwrap(),
+    })
+}
+
+impl Display for BenchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} ({} runs after {} warmup runs)",
+            self.puzzle, self.runs, self.warmup
+        )?;
+        writeln!(
+            f,
+            "{:<7}{:>14}{:>14}{:>14}{:>14}{:>14}",
+            "", "min", "mean", "median", "p95", "stddev"
+        )?;
+        for (name, stats) in [
+            ("load", &self.load),
+            ("parse", &self.parse),
+            ("solve", &self.solve),
+        ] {
+            writeln!(
+                f,
+                "{:<7}{:>14}{:>14}{:>14}{:>14}{:>14}",
+                name,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.p95),
+                format!("{:.2?}", stats.stddev),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|m| Duration::from_millis(*m)).collect()
+    }
+
+    #[test]
+    fn test_it_calculates_stats() {
+        let stats = Stats::from_samples(&ms(&[4, 2, 8, 6])).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(8));
+        // sqrt(((4-5)^2 + (2-5)^2 + (8-5)^2 + (6-5)^2) / 4) = sqrt(5)
+        let stddev = stats.stddev.as_secs_f64() * 1000.0;
+        assert!((stddev - 5f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_it_calculates_p95_using_nearest_rank() {
+        let samples = ms(&(1..=40).collect::<Vec<_>>());
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.p95, Duration::from_millis(38));
+        assert_eq!(stats.median, Duration::from_micros(20_500));
+    }
+
+    #[test]
+    fn test_it_has_no_stats_without_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+}
@@ -1,37 +1,55 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use regex::Regex;
 use std::sync::OnceLock;
 
-pub fn part1(input: &str) -> Result<usize> {
-    let res = input.lines().map(parse_line_part1).sum();
-    Ok(res)
+use crate::parse::ParseError;
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "Trebuchet?!";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 1, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 1, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 1, 1, EXAMPLE1, 142));
+    registry.register_example(Example::new(2023, 1, 2, EXAMPLE2, 281));
+}
+
+pub fn parse(input: &str) -> Result<Vec<String>> {
+    for (y, line) in input.lines().enumerate() {
+        if let Some(x) = line.chars().position(|c| !c.is_ascii_alphanumeric()) {
+            return Err(ParseError::at(input, y, x, "a letter or digit").into());
+        }
+    }
+    Ok(input.lines().map(String::from).collect())
+}
+
+pub fn part1(lines: &[String]) -> Result<usize> {
+    lines.iter().map(|l| parse_line_part1(l)).sum()
 }
 
-fn parse_line_part1(line: &str) -> usize {
-    let mut digits = line
-        .chars()
-        .filter(|p| p.is_ascii_digit())
-        .map(|d| d.to_digit(10).unwrap());
+fn parse_line_part1(line: &str) -> Result<usize> {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
 
-    let first = digits.next().unwrap();
-    let last = digits.last();
+    let Some(first) = digits.next() else {
+        bail!("there are no digits in the line '{}'", line);
+    };
+    let last = digits.next_back();
 
     if let Some(last) = last {
-        return (first as usize * 10) + last as usize;
+        return Ok((first as usize * 10) + last as usize);
     }
 
-    (first as usize * 10) + first as usize
+    Ok((first as usize * 10) + first as usize)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let res = input.lines().map(parse_line_part2).sum();
-    Ok(res)
+pub fn part2(lines: &[String]) -> Result<usize> {
+    lines.iter().map(|l| parse_line_part2(l)).sum()
 }
 
 static FWD_RE: OnceLock<Regex> = OnceLock::new();
 static BWD_RE: OnceLock<Regex> = OnceLock::new();
 
-fn parse_line_part2(line: &str) -> usize {
+fn parse_line_part2(line: &str) -> Result<usize> {
     let re1 = FWD_RE.get_or_init(|| {
         Regex::new(r"1|2|3|4|5|6|7|8|9|one|two|three|four|five|six|seven|eight|nine").unwrap()
     });
@@ -52,9 +70,9 @@ fn parse_line_part2(line: &str) -> usize {
             "7" | "seven" => 7,
             "8" | "eight" => 8,
             "9" | "nine" => 9,
-            _ => panic!("bad regex match"),
+            _ => unreachable!("the regex only matches digits"),
         })
-        .unwrap();
+        .ok_or_else(|| anyhow!("there are no digits in the line '{}'", line))?;
 
     // Find the last number on the line
     let reverse_line: String = line.chars().rev().collect();
@@ -70,29 +88,19 @@ fn parse_line_part2(line: &str) -> usize {
             "7" | "neves" => 7,
             "8" | "thgie" => 8,
             "9" | "enin" => 9,
-            _ => panic!("bad regex match"),
+            _ => unreachable!("the regex only matches digits"),
         })
-        .unwrap();
+        .ok_or_else(|| anyhow!("there are no digits in the line '{}'", line))?;
 
-    (first * 10) + last
+    Ok((first * 10) + last)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT1: &str = "1abc2
+const EXAMPLE1: &str = "1abc2
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
 
-    #[test]
-    fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT1).unwrap();
-        assert_eq!(res, 142);
-    }
-
-    const INPUT2: &str = "two1nine
+const EXAMPLE2: &str = "two1nine
 eightwothree
 abcone2threexyz
 xtwone3four
@@ -100,9 +108,26 @@ xtwone3four
 zoneight234
 7pqrstsixteen";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        let res = part1(&parse(EXAMPLE1).unwrap()).unwrap();
+        assert_eq!(res, 142);
+    }
+
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT2).unwrap();
+        let res = part2(&parse(EXAMPLE2).unwrap()).unwrap();
         assert_eq!(res, 281);
     }
+
+    #[test]
+    fn test_lines_without_digits_are_an_error() {
+        let lines = parse("1abc2\nabc").unwrap();
+        assert!(part1(&lines).is_err());
+        assert!(part2(&lines).is_err());
+    }
 }
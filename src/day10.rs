@@ -1,35 +1,40 @@
-use std::{
-    cell::OnceCell,
-    collections::{HashMap, HashSet},
-    fmt::Display,
-};
-
-use crate::algorithm::shoelace;
-use crate::point::Point;
-use anyhow::{bail, Result};
-
-pub fn part1(input: &str) -> Result<usize> {
-    let grid = Grid::try_from(input)?;
-    println!("{}", grid);
-    Ok(grid.dist_to_farthest_point())
+use std::{collections::HashSet, fmt::Display};
+
+use crate::algorithm::{search, shoelace};
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::point::Point2;
+use crate::solver::{Example, FnSolver, Registry};
+use anyhow::{anyhow, bail, Result};
+
+const NAME: &str = "Pipe Maze";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 10, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 10, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 10, 1, EXAMPLE1, 4));
+    registry.register_example(Example::new(2023, 10, 2, EXAMPLE2, 10));
 }
 
-pub fn part2(input: &str) -> Result<i32> {
-    let grid = Grid::try_from(input)?;
-    println!("{}", grid);
-    Ok(grid.loop_enclosed_point_count())
+pub fn parse(input: &str) -> Result<Maze> {
+    Maze::try_from(input)
 }
 
-struct Grid {
+pub fn part1(maze: &Maze) -> Result<usize> {
+    Ok(maze.dist_to_farthest_point())
+}
+
+pub fn part2(maze: &Maze) -> Result<i32> {
+    maze.loop_enclosed_point_count()
+}
+
+pub struct Maze {
     start: Coord,
-    w: usize,
-    h: usize,
-    pipes: HashMap<Coord, Pipe>,
-    loop_coords: OnceCell<Vec<Coord>>,
+    pipes: Grid<Option<Pipe>>,
+    loop_coords: Vec<Coord>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord(i32, i32);
+type Coord = Point2<usize>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Pipe {
@@ -41,121 +46,105 @@ enum Pipe {
     SE,
 }
 
-impl Grid {
-    pub fn new(start: Coord, w: usize, h: usize, pipes: HashMap<Coord, Pipe>) -> Self {
-        Self {
+impl Maze {
+    fn new(start: Coord, pipes: Grid<Option<Pipe>>) -> Result<Self> {
+        let loop_coords = find_loop(start, &pipes)?;
+        Ok(Self {
             start,
-            w,
-            h,
             pipes,
-            loop_coords: OnceCell::new(),
-        }
-    }
-
-    pub fn loop_coords(&self) -> &Vec<Coord> {
-        self.loop_coords.get_or_init(|| {
-            let start_pipe = self.pipes.get(&self.start).unwrap();
-            // Choose one of the start pipe's exits (doesn't matter which)
-            let mut pipe_pos = start_pipe.exits(self.start).into_iter().next().unwrap();
-            let mut prev_pos = self.start;
-            let mut pipe_coords = vec![self.start];
-
-            while pipe_pos != self.start {
-                pipe_coords.push(pipe_pos);
-                let curr = pipe_pos;
-                pipe_pos = self
-                    .pipes
-                    .get(&pipe_pos)
-                    .unwrap()
-                    .other_exit(pipe_pos, prev_pos)
-                    .unwrap();
-                prev_pos = curr;
-            }
-            pipe_coords
+            loop_coords,
         })
     }
 
     pub fn dist_to_farthest_point(&self) -> usize {
-        self.loop_coords().len() / 2
+        self.loop_coords.len() / 2
     }
 
-    pub fn loop_enclosed_point_count(&self) -> i32 {
+    pub fn loop_enclosed_point_count(&self) -> Result<i32> {
         let vertices = self
-            .loop_coords()
+            .loop_coords
             .iter()
-            .chain(std::iter::once(&self.loop_coords()[0]))
-            .collect::<Vec<_>>();
+            .chain(std::iter::once(&self.start))
+            .map(|c| {
+                c.cast()
+                    .ok_or_else(|| anyhow!("{} is too far out to measure", c))
+            })
+            .collect::<Result<Vec<Point2<i32>>>>()?;
 
-        shoelace::enclosed_area(&vertices)
+        Ok(shoelace::enclosed_area(&vertices))
     }
 }
 
-impl Coord {
-    /// Is the coordinate in the grid?
-    pub fn is_valid(&self) -> bool {
-        self.0 >= 0 && self.1 >= 0
+/// Follows the pipes from `start` until they lead back to it, giving the
+/// coordinates of every pipe in the loop in order
+fn find_loop(start: Coord, pipes: &Grid<Option<Pipe>>) -> Result<Vec<Coord>> {
+    if pipes[start.into()].is_none() {
+        bail!("there is no pipe at the start {}", start);
     }
-
-    /// Gets valid neighbouring coordinates
-    pub fn neighbours(&self) -> Vec<Coord> {
-        vec![
-            Coord(self.0, self.1 - 1),
-            Coord(self.0, self.1 + 1),
-            Coord(self.0 - 1, self.1),
-            Coord(self.0 + 1, self.1),
-        ]
-        .into_iter()
-        .filter(Self::is_valid)
-        .collect()
+    // Going depth first follows the loop all the way round one way, as each
+    // pipe only leads on to the one it wasn't reached from
+    let loop_coords = search::dfs(start, |pos| connections(pipes, *pos));
+    if let Some(end) = loop_coords
+        .iter()
+        .find(|pos| connections(pipes, **pos).len() != 2)
+    {
+        bail!("the loop from {} is broken at {}", start, end);
     }
+    Ok(loop_coords)
 }
 
-impl Point<i32> for &Coord {
-    fn x(&self) -> i32 {
-        self.0
-    }
-
-    fn y(&self) -> i32 {
-        self.1
-    }
+/// Gets the coordinates of the pipes that the pipe at `pos` leads to, which
+/// lead back to it as well
+fn connections(pipes: &Grid<Option<Pipe>>, pos: Coord) -> Vec<Coord> {
+    let Some(Some(pipe)) = pipes.get(pos.into()) else {
+        return vec![];
+    };
+    pipe.exits(pos)
+        .into_iter()
+        .filter(|exit| {
+            pipes
+                .get((*exit).into())
+                .and_then(Option::as_ref)
+                .is_some_and(|p| p.exits(*exit).contains(&pos))
+        })
+        .collect()
 }
 
 impl Pipe {
     /// For a Pipe at `pipe_pos`, gets the coordinates of the valid exits
-    /// i.e. exits that are not out of bounds of the grid
+    /// i.e. exits that aren't above or left of the grid
     pub fn exits(&self, pipe_pos: Coord) -> Vec<Coord> {
-        let deltas = match self {
-            Self::NS => vec![(0, -1), (0, 1)],
-            Self::EW => vec![(1, 0), (-1, 0)],
-            Self::NE => vec![(0, -1), (1, 0)],
-            Self::NW => vec![(0, -1), (-1, 0)],
-            Self::SW => vec![(0, 1), (-1, 0)],
-            Self::SE => vec![(0, 1), (1, 0)],
-        };
-        deltas
-            .iter()
-            .map(|(dx, dy)| Coord(pipe_pos.0 + dx, pipe_pos.1 + dy))
-            .filter(|c| c.is_valid())
+        self.directions()
+            .into_iter()
+            .filter_map(|dir| pipe_pos.step(dir))
             .collect()
     }
 
-    /// For a Pipe at `pipe_pos`, with one exit at `exit`, gets the coordinates
-    /// of the other exit (assuming it would exit to a valid grid coordinate.
-    pub fn other_exit(&self, pipe_pos: Coord, exit: Coord) -> Option<Coord> {
-        self.exits(pipe_pos).into_iter().find(|e| *e != exit)
+    /// Gets the directions the pipe's two ends point in
+    fn directions(&self) -> [Direction; 2] {
+        match self {
+            Self::NS => [Direction::North, Direction::South],
+            Self::EW => [Direction::East, Direction::West],
+            Self::NE => [Direction::North, Direction::East],
+            Self::NW => [Direction::North, Direction::West],
+            Self::SW => [Direction::South, Direction::West],
+            Self::SE => [Direction::South, Direction::East],
+        }
     }
 }
 
-impl Display for Grid {
+impl Display for Maze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "{}x{} grid. Start is at {}\n\n",
-            self.w, self.h, self.start
+            self.pipes.width(),
+            self.pipes.height(),
+            self.start
         )?;
-        for y in 0..self.h {
-            for x in 0..self.w {
-                match self.pipes.get(&Coord(x as i32, y as i32)) {
+        for row in self.pipes.rows() {
+            for pipe in row {
+                match pipe {
                     None => write!(f, ".")?,
                     Some(pipe) => write!(f, "{}", pipe)?,
                 }
@@ -167,12 +156,6 @@ impl Display for Grid {
     }
 }
 
-impl Display for Coord {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{})", self.0, self.1)
-    }
-}
-
 impl Display for Pipe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let char = match self {
@@ -187,54 +170,47 @@ impl Display for Pipe {
     }
 }
 
-impl TryFrom<&str> for Grid {
+impl TryFrom<char> for Pipe {
+    type Error = ();
+
+    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
+        match value {
+            '|' => Ok(Pipe::NS),
+            '-' => Ok(Pipe::EW),
+            'L' => Ok(Pipe::NE),
+            'J' => Ok(Pipe::NW),
+            '7' => Ok(Pipe::SW),
+            'F' => Ok(Pipe::SE),
+            _ => Err(()),
+        }
+    }
+}
+
+impl TryFrom<&str> for Maze {
     type Error = anyhow::Error;
 
     fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
-        let mut start = None;
-        let mut pipes = HashMap::new();
-        let mut w = 0;
-        let mut h = 0;
-        for (y, line) in value.lines().enumerate() {
-            h += 1;
-            if w == 0 {
-                w = line.chars().count()
-            }
-            for (x, char) in line.chars().enumerate() {
-                let mut pipe = None;
-                match char {
-                    'S' => start = Some(Coord(x as i32, y as i32)),
-                    '|' => pipe = Some(Pipe::NS),
-                    '-' => pipe = Some(Pipe::EW),
-                    'L' => pipe = Some(Pipe::NE),
-                    'J' => pipe = Some(Pipe::NW),
-                    '7' => pipe = Some(Pipe::SW),
-                    'F' => pipe = Some(Pipe::SE),
-                    '.' => {}
-                    _ => bail!("unexpected character {}", char),
-                }
-                if pipe.is_some() {
-                    pipes.insert(Coord(x as i32, y as i32), pipe.take().unwrap());
-                }
-            }
-        }
-
-        let Some(start) = start else {
+        let tiles = Grid::parse(
+            value,
+            |c| "|-LJ7FS.".contains(c).then_some(c),
+            "a pipe, '.' or 'S'",
+        )?;
+        let Some(start) = tiles.position(|c| *c == 'S') else {
             bail!("no start position found in input");
         };
+        let start = Coord::from(start);
+        let mut pipes = tiles.map(|c| Pipe::try_from(*c).ok());
 
         // Work out which kind of pipe is at the start
-        let start_connecting_coords = start
-            .neighbours()
-            .into_iter()
-            .map(|c| (c, pipes.get(&c)))
-            .filter(|(c, p)| {
-                if let Some(p) = p {
-                    return p.exits(*c).iter().any(|e| *e == start);
+        let start_connecting_coords = pipes
+            .neighbours4(start.into())
+            .map(Coord::from)
+            .filter(|c| {
+                if let Some(p) = &pipes[(*c).into()] {
+                    return p.exits(*c).contains(&start);
                 }
                 false
             })
-            .map(|(c, _p)| c)
             .collect::<HashSet<Coord>>();
         let start_pipe = vec![Pipe::NS, Pipe::EW, Pipe::NE, Pipe::NW, Pipe::SE, Pipe::SW]
             .into_iter()
@@ -246,23 +222,37 @@ impl TryFrom<&str> for Grid {
                 exits
                     .into_iter()
                     .all(|e| start_connecting_coords.contains(&e))
-            })
-            .expect("expected to have a start pipe");
-        pipes.insert(start, start_pipe);
+            });
+        let Some(start_pipe) = start_pipe else {
+            bail!("the start {} doesn't connect to exactly two pipes", start);
+        };
+        pipes[start.into()] = Some(start_pipe);
 
-        Ok(Grid::new(start, w, h, pipes))
+        Maze::new(start, pipes)
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT1: &str = "-L|F7
+const EXAMPLE1: &str = "-L|F7
 7S-7|
 L|7||
 -L-J|
 L|-JF";
+
+const EXAMPLE2: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     const INPUT2: &str = "7-F7-
 .FJ|7
 SJLL7
@@ -271,10 +261,10 @@ LJ.LJ";
 
     #[test]
     fn test_part1_gives_correct_answer() {
-        let cases = vec![(INPUT1, 4), (INPUT2, 8)];
+        let cases = vec![(EXAMPLE1, 4), (INPUT2, 8)];
 
         for (input, expect) in cases {
-            let res = part1(input).unwrap();
+            let res = part1(&parse(input).unwrap()).unwrap();
             assert_eq!(res, expect);
         }
     }
@@ -300,24 +290,19 @@ L--J.L7...LJS7F-7L7.
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
 
-    const INPUT5: &str = "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L";
-
     #[test]
     fn test_part2_gives_correct_answer() {
-        let cases = vec![(INPUT1, 1), (INPUT3, 4), (INPUT4, 8), (INPUT5, 10)];
+        let cases = vec![(EXAMPLE1, 1), (INPUT3, 4), (INPUT4, 8), (EXAMPLE2, 10)];
 
         for (input, expect) in cases {
-            let res = part2(input).unwrap();
+            let res = part2(&parse(input).unwrap()).unwrap();
             assert_eq!(res, expect);
         }
     }
+
+    #[test]
+    fn test_a_start_without_a_loop_is_an_error() {
+        assert!(parse("...\n.S-\n...").is_err());
+        assert!(parse("S-7\n|.|\nL-.").is_err());
+    }
 }
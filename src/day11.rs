@@ -1,21 +1,47 @@
 use std::fmt::Display;
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use itertools::Itertools;
 
-pub fn part1(input: &str) -> Result<usize> {
-    solve(input, 2)
+use crate::grid::Grid;
+use crate::point::Point2;
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "Cosmic Expansion";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 11, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 11, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 11, 1, EXAMPLE, 374));
+    registry.register_example(Example::new(2023, 11, 2, EXAMPLE, 82000210));
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    solve(input, 1_000_000)
+pub fn parse(input: &str) -> Result<Image> {
+    Image::try_from(input)
 }
 
-/// One empty row/column in `input` will be replaced with `scaling_factor` empty
+pub fn part1(image: &Image) -> Result<usize> {
+    Ok(solve(image, 2))
+}
+
+pub fn part2(image: &Image) -> Result<usize> {
+    Ok(solve(image, 1_000_000))
+}
+
+/// One empty row/column in `image` will be replaced with `scaling_factor` empty
 /// rows/columns.
-fn solve(input: &str, scaling_factor: usize) -> Result<usize> {
-    let universe = Universe::try_from((input, scaling_factor))?;
-    Ok(universe.galaxy_pair_distances().iter().sum())
+fn solve(image: &Image, scaling_factor: usize) -> usize {
+    let universe = image.expand(scaling_factor);
+    universe.galaxy_pair_distances().iter().sum()
+}
+
+/// The galaxies as they appear in the input, before the universe expands.
+pub struct Image {
+    galaxies: Vec<Coord>,
+    /// How many empty columns there are to the left of each column
+    x_offsets: Vec<usize>,
+    /// How many empty rows there are above each row
+    y_offsets: Vec<usize>,
 }
 
 struct Universe {
@@ -24,16 +50,26 @@ struct Universe {
     galaxies: Vec<Coord>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Coord {
-    x: usize,
-    y: usize,
+type Coord = Point2<usize>;
+
+impl Image {
+    fn expand(&self, scaling_factor: usize) -> Universe {
+        let galaxies = self
+            .galaxies
+            .iter()
+            .map(|Coord { x, y }| Coord {
+                x: x + self.x_offsets[*x] * (scaling_factor - 1),
+                y: y + self.y_offsets[*y] * (scaling_factor - 1),
+            })
+            .collect();
+        Universe::new(galaxies)
+    }
 }
 
 impl Universe {
     fn new(galaxies: Vec<Coord>) -> Self {
-        let w = galaxies.iter().map(|c| c.x).max().unwrap() + 1;
-        let h = galaxies.iter().map(|c| c.y).max().unwrap() + 1;
+        let w = galaxies.iter().map(|c| c.x + 1).max().unwrap_or(0);
+        let h = galaxies.iter().map(|c| c.y + 1).max().unwrap_or(0);
         Self { w, h, galaxies }
     }
 
@@ -41,18 +77,11 @@ impl Universe {
         self.galaxies
             .iter()
             .tuple_combinations()
-            .map(|(a, b)| a.distance_to(b))
+            .map(|(a, b)| a.manhattan_distance(b))
             .collect()
     }
 }
 
-impl Coord {
-    fn distance_to(&self, other: &Self) -> usize {
-        (self.x as isize - other.x as isize).unsigned_abs()
-            + (self.y as isize - other.y as isize).unsigned_abs()
-    }
-}
-
 impl Display for Universe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for y in 0..self.h {
@@ -69,69 +98,51 @@ impl Display for Universe {
     }
 }
 
-impl TryFrom<(&str, usize)> for Universe {
+impl TryFrom<&str> for Image {
     type Error = anyhow::Error;
 
-    fn try_from(value: (&str, usize)) -> std::result::Result<Self, Self::Error> {
-        let (input, scaling_factor) = value;
-        let h = input.lines().count();
-        let w = input.lines().next().unwrap().len();
-        let mut map: Vec<Vec<char>> = vec![];
-        let mut x_offsets = vec![0usize; w];
-        let mut y_offsets = vec![0usize; h];
+    fn try_from(input: &str) -> std::result::Result<Self, Self::Error> {
+        let grid = Grid::parse(
+            input,
+            |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            },
+            "'#' or '.'",
+        )?;
+        let mut x_offsets = vec![0usize; grid.width()];
+        let mut y_offsets = vec![0usize; grid.height()];
 
         // Find empty rows
-        for (y, line) in input.lines().enumerate() {
-            let mut line_empty = true;
-            let mut line_chars = vec![];
-            for c in line.chars() {
-                match c {
-                    '#' => line_empty = false,
-                    '.' => {}
-                    c => bail!("unexpected character in input: {}", c),
-                }
-                line_chars.push(c);
-            }
-            map.push(line_chars);
-            if line_empty {
+        for (y, mut row) in grid.rows().map(|r| r.iter()).enumerate() {
+            if !row.any(|is_galaxy| *is_galaxy) {
                 increment_offsets(&mut y_offsets, y);
             }
         }
 
         // Find empty columns
-        map = transpose(map);
-        for (x, row) in map.iter().enumerate() {
-            let mut row_empty = true;
-            for c in row.iter() {
-                if *c == '#' {
-                    row_empty = false;
-                }
-            }
-            if row_empty {
+        for (x, mut column) in grid.columns().enumerate() {
+            if !column.any(|is_galaxy| *is_galaxy) {
                 increment_offsets(&mut x_offsets, x);
             }
         }
 
-        // Map the
-        let mut galaxies = vec![];
-        for (y, row) in input.lines().enumerate() {
-            for (x, c) in row.chars().enumerate() {
-                if c == '#' {
-                    let x_offset = x_offsets[x] * (scaling_factor - 1);
-                    let y_offset = y_offsets[y] * (scaling_factor - 1);
-                    galaxies.push(Coord {
-                        x: x + x_offset,
-                        y: y + y_offset,
-                    });
-                }
-            }
-        }
-
-        Ok(Universe::new(galaxies))
+        let galaxies = grid
+            .iter()
+            .filter(|(_, is_galaxy)| **is_galaxy)
+            .map(|((x, y), _)| Coord { x, y })
+            .collect();
+
+        Ok(Image {
+            galaxies,
+            x_offsets,
+            y_offsets,
+        })
     }
 }
 
-fn increment_offsets(offsets: &mut Vec<usize>, from: usize) {
+fn increment_offsets(offsets: &mut [usize], from: usize) {
     for i in (from + 1)..offsets.len() {
         if let Some(offset) = offsets.get_mut(i) {
             *offset += 1;
@@ -139,25 +150,7 @@ fn increment_offsets(offsets: &mut Vec<usize>, from: usize) {
     }
 }
 
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    assert!(!v.is_empty());
-    let len = v[0].len();
-    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<T>>()
-        })
-        .collect()
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "...#......
+const EXAMPLE: &str = "...#......
 .......#..
 #.........
 ..........
@@ -168,18 +161,23 @@ mod test {
 .......#..
 #...#.....";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 374);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res_10 = solve(INPUT, 10).unwrap();
+        let image = parse(EXAMPLE).unwrap();
+        let res_10 = solve(&image, 10);
         assert_eq!(res_10, 1030);
 
-        let res_100 = solve(INPUT, 100).unwrap();
+        let res_100 = solve(&image, 100);
         assert_eq!(res_100, 8410);
     }
 }
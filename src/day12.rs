@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use anyhow::{bail, Result};
 use nom::{
     character::complete::{char, one_of},
@@ -10,10 +8,24 @@ use nom::{
 };
 use rayon::prelude::*;
 
+use crate::algorithm::memo::Memo;
 use crate::parse::{number, parse_lines_to_vec};
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "Hot Springs";
 
-pub fn part1(input: &str) -> Result<usize> {
-    let rows = parse_lines_to_vec(input, parse_row)?;
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 12, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 12, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 12, 1, EXAMPLE, 21));
+    registry.register_example(Example::new(2023, 12, 2, EXAMPLE, 525152));
+}
+
+pub fn parse(input: &str) -> Result<Vec<Row>> {
+    parse_lines_to_vec(input, parse_row)
+}
+
+pub fn part1(rows: &[Row]) -> Result<usize> {
     let res = rows
         .par_iter()
         .map(|r| r.possible_arrangement_count())
@@ -21,8 +33,7 @@ pub fn part1(input: &str) -> Result<usize> {
     Ok(res)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let rows = parse_lines_to_vec(input, parse_row)?;
+pub fn part2(rows: &[Row]) -> Result<usize> {
     let res = rows
         .par_iter()
         .map(|r| r.multiply(5).possible_arrangement_count())
@@ -30,8 +41,11 @@ pub fn part2(input: &str) -> Result<usize> {
     Ok(res)
 }
 
+/// Counts the arrangements from a spring index and group index
+type Recurse<'a> = dyn FnMut((usize, usize)) -> usize + 'a;
+
 #[derive(Debug)]
-struct Row {
+pub struct Row {
     springs: Vec<Spring>,
     group_sizes: Vec<usize>,
 }
@@ -70,20 +84,21 @@ impl Row {
     }
 
     pub fn possible_arrangement_count(&self) -> usize {
-        let mut cache = HashMap::new();
-        self.count_arrangements(0, 0, &mut cache)
+        Memo::new(|recurse: &mut Recurse, (spring_idx, group_idx)| {
+            self.count_arrangements(spring_idx, group_idx, recurse)
+        })
+        .call((0, 0))
     }
 
+    /// Counts the arrangements of the springs from `spring_idx` onwards that
+    /// fit the groups from `group_idx` onwards, using `recurse` to count the
+    /// rest of the springs and groups
     fn count_arrangements(
         &self,
         spring_idx: usize,
         group_idx: usize,
-        cache: &mut HashMap<(usize, usize), usize>,
+        recurse: &mut Recurse,
     ) -> usize {
-        if let Some(cached) = cache.get(&(spring_idx, group_idx)) {
-            return *cached;
-        }
-
         // check if the current group can be satisfied
         let groups_result = self.group_sizes.get(group_idx).map_or(0, |group_size| {
             // check we have enough springs left for the group
@@ -101,17 +116,16 @@ impl Row {
                 return 0;
             }
             // we have a possibly valid group
-            self.count_arrangements(spring_idx + group_size + 1, group_idx + 1, cache)
+            recurse((spring_idx + group_size + 1, group_idx + 1))
         });
 
         let spring_result = match self.springs.get(spring_idx) {
             // if we got through all of the springs/groups, we found a valid arrangement
             None => usize::from(group_idx >= self.group_sizes.len()),
             Some(Spring::Damaged) => 0,
-            Some(_) => self.count_arrangements(spring_idx + 1, group_idx, cache),
+            Some(_) => recurse((spring_idx + 1, group_idx)),
         };
 
-        cache.insert((spring_idx, group_idx), groups_result + spring_result);
         groups_result + spring_result
     }
 }
@@ -142,26 +156,26 @@ fn parse_row(input: &str) -> IResult<&str, Row> {
     )(input)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "???.### 1,1,3
+const EXAMPLE: &str = "???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
 ????.#...#... 4,1,1
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 21);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 525152);
     }
 }
@@ -1,24 +1,32 @@
 use anyhow::Result;
-use nom::{
-    character::complete::{line_ending, one_of},
-    combinator::{map, opt},
-    multi::{many1, separated_list1},
-    sequence::terminated,
-    IResult,
-};
-
-use crate::parse::parse_lines_to_vec;
-
-pub fn part1(input: &str) -> Result<usize> {
-    solve(input, false)
+use nom::{combinator::map, IResult};
+
+use crate::grid::Grid;
+use crate::parse::{char_grid, parse_all_to, sections};
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "Point of Incidence";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 13, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 13, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 13, 1, EXAMPLE, 405));
+    registry.register_example(Example::new(2023, 13, 2, EXAMPLE, 400));
+}
+
+pub fn parse(input: &str) -> Result<Vec<Pattern>> {
+    parse_all_to(input, sections(parse_pattern))
+}
+
+pub fn part1(patterns: &[Pattern]) -> Result<usize> {
+    solve(patterns, false)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    solve(input, true)
+pub fn part2(patterns: &[Pattern]) -> Result<usize> {
+    solve(patterns, true)
 }
 
-fn solve(input: &str, find_smudges: bool) -> Result<usize> {
-    let patterns = parse_lines_to_vec(input, parse_pattern)?;
+fn solve(patterns: &[Pattern], find_smudges: bool) -> Result<usize> {
     let res = patterns
         .iter()
         .map(|p| {
@@ -35,7 +43,7 @@ fn solve(input: &str, find_smudges: bool) -> Result<usize> {
 }
 
 #[derive(Debug)]
-struct Pattern {
+pub struct Pattern {
     /// Rows from the input with each row converted to a usize, where enabled bits
     /// represent '#'s.
     rows: Vec<usize>,
@@ -49,7 +57,7 @@ enum MirrorAlignment {
 }
 
 impl Pattern {
-    pub fn reflection_index(&self, dir: MirrorAlignment, find_smudges: bool) -> Option<usize> {
+    fn reflection_index(&self, dir: MirrorAlignment, find_smudges: bool) -> Option<usize> {
         let rows = match dir {
             MirrorAlignment::Horizontal => &self.rows,
             MirrorAlignment::Vertical => &self.cols,
@@ -101,39 +109,20 @@ impl Pattern {
 
 /// Requires that no lines in the input are longer than 64 characters
 fn parse_pattern(input: &str) -> IResult<&str, Pattern> {
-    let character = one_of(".#");
-    let row = many1(character);
-    // terminated by an optional line ending, because the last pattern
-    // in the list doesn't have a newline after it.
-    map(
-        terminated(separated_list1(line_ending, row), opt(line_ending)),
-        |char_rows| {
-            let rows = char_rows
-                .iter()
-                .map(|chars| hashes_to_bits(&chars[..]))
-                .collect::<Vec<_>>();
-
-            let w = char_rows[0].len();
-            let h = char_rows.len();
-            let char_cols: Vec<Vec<_>> = (0..w)
-                .map(|col| (0..h).map(|row| char_rows[row][col]).rev().collect())
-                .collect();
-            let cols = char_cols
-                .iter()
-                .map(|chars| hashes_to_bits(&chars[..]))
-                .collect::<Vec<_>>();
-
-            Pattern { rows, cols }
-        },
-    )(input)
+    let cell = |c| ".#".contains(c).then_some(c);
+    map(char_grid(cell), |grid: Grid<char>| {
+        let rows = grid.rows().map(hashes_to_bits).collect();
+        let cols = grid.columns().map(hashes_to_bits).collect();
+        Pattern { rows, cols }
+    })(input)
 }
 
 /// Takes a row of characters from the input and converts to
 /// a binary value where '#' characters are represented by 1
 /// and '.' characters are 0
-fn hashes_to_bits(chars: &[char]) -> usize {
+fn hashes_to_bits<'a>(chars: impl IntoIterator<Item = &'a char>) -> usize {
     let mut val: usize = 0;
-    for c in chars.iter() {
+    for c in chars {
         val <<= 1;
         if *c == '#' {
             val |= 1;
@@ -142,11 +131,7 @@ fn hashes_to_bits(chars: &[char]) -> usize {
     val
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT1: &str = "#.##..##.
+const EXAMPLE: &str = "#.##..##.
 ..#.##.#.
 ##......#
 ##......#
@@ -162,6 +147,10 @@ mod test {
 ..##..###
 #....#..#";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     const INPUT2: &str = "##..#.#......#..#
 .....#..#.#######
 ...########...##.
@@ -180,16 +169,16 @@ mod test {
 
     #[test]
     fn test_part1_gives_correct_answer() {
-        let cases = vec![(INPUT1, 405), (INPUT2, 1)];
+        let cases = vec![(EXAMPLE, 405), (INPUT2, 1)];
         for (input, expect) in cases {
-            let res = part1(input).unwrap();
+            let res = part1(&parse(input).unwrap()).unwrap();
             assert_eq!(res, expect);
         }
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT1).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 400);
     }
 }
@@ -1,46 +1,44 @@
-use crate::parse::parse_all_to;
-use anyhow::{bail, Result};
-use nom::character::complete::{line_ending, one_of};
-use nom::combinator::{map, opt};
-use nom::multi::{many1, separated_list1};
-use nom::sequence::terminated;
+use crate::algorithm::cycle;
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::parse::{char_grid, parse_all_to};
+use crate::solver::{Example, FnSolver, Registry};
+use anyhow::Result;
+use nom::combinator::map;
 use nom::IResult;
 use std::fmt::{Display, Formatter};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let mut platform = parse_all_to(input, parse_platform)?;
-    platform.slide_north();
-    Ok(platform.calculate_load())
-}
+const NAME: &str = "Parabolic Reflector Dish";
 
-pub fn part2(input: &str) -> Result<usize> {
-    let mut platform = parse_all_to(input, parse_platform)?;
-    // let the periodicity kick in by spinning for a bit
-    platform.spin_cycle(200);
-    // these values should now all be repeating with some period
-    let values = platform.spin_cycle(500);
-    if let Some(period) = find_period_length(&values) {
-        return Ok(values[(1_000_000_000 - 200 - 1) % period]);
-    }
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 14, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 14, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 14, 1, EXAMPLE, 136));
+    registry.register_example(Example::new(2023, 14, 2, EXAMPLE, 64));
+}
 
-    bail!("Could not find period in the platform loads")
+pub fn parse(input: &str) -> Result<Platform> {
+    parse_all_to(input, parse_platform)
 }
 
-fn find_period_length(values: &[usize]) -> Option<usize> {
-    (1..values.len() / 2).find(|&p| has_period(values, p))
+pub fn part1(platform: &Platform) -> Result<usize> {
+    let mut platform = platform.clone();
+    platform.slide_north();
+    Ok(platform.calculate_load())
 }
 
-fn has_period(values: &[usize], length: usize) -> bool {
-    (0..values.len()).all(|i| values[i] == values[i % length])
+pub fn part2(platform: &Platform) -> Result<usize> {
+    let mut platform = platform.clone();
+    platform.spin_cycle(1_000_000_000);
+    Ok(platform.calculate_load())
 }
 
-struct Platform {
-    w: usize,
-    h: usize,
-    rocks: Vec<Vec<Option<Rock>>>,
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    rocks: Grid<Option<Rock>>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Rock {
     Round,
     Square,
@@ -52,25 +50,22 @@ enum SpanState {
     Clear,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
 impl Platform {
-    pub fn spin_cycle(&mut self, iterations: usize) -> Vec<usize> {
-        (0..iterations)
-            .map(|_| {
-                self.slide_north();
-                self.slide_west();
-                self.slide_south();
-                self.slide_east();
-                self.calculate_load()
-            })
-            .collect()
+    /// Spins the platform `iterations` times. The rocks soon end up going
+    /// round in circles, so once they're somewhere they've been before the
+    /// rest of the spins are skipped.
+    pub fn spin_cycle(&mut self, iterations: usize) {
+        *self = cycle::nth_state(self.clone(), Self::spun, iterations);
+    }
+
+    /// Gets the platform after sliding the rocks north, west, south then east
+    fn spun(&self) -> Self {
+        let mut platform = self.clone();
+        platform.slide_north();
+        platform.slide_west();
+        platform.slide_south();
+        platform.slide_east();
+        platform
     }
 
     pub fn slide_north(&mut self) {
@@ -92,14 +87,14 @@ impl Platform {
     fn slide_vertical(&mut self, direction: Direction) {
         let (init_rock_idx, rock_idx_adjuster): (usize, fn(usize) -> usize) = match direction {
             Direction::North => (0, |ri| ri + 1),
-            Direction::South => (self.h - 1, |ri| ri - 1),
+            Direction::South => (self.rocks.height() - 1, |ri| ri - 1),
             _ => unreachable!("slide_vertical only expects vertical directions"),
         };
-        for x in 0..self.w {
+        for x in 0..self.rocks.width() {
             let mut span_state: Option<SpanState> = None;
             let mut last_rock_idx: Option<usize> = None;
             for y in self.iter_indices(direction) {
-                let cur_rock = self.rocks[y][x];
+                let cur_rock = self.rocks[(x, y)];
                 match (cur_rock, span_state) {
                     (None, None) => {
                         span_state = Some(SpanState::Clear);
@@ -110,9 +105,9 @@ impl Platform {
                     }
                     (None, Some(SpanState::Clear)) => continue,
                     (Some(Rock::Round), Some(SpanState::Clear)) => {
-                        let rock = self.rocks[y][x].take();
+                        let rock = self.rocks[(x, y)].take();
                         let rock_idx = last_rock_idx.map_or(init_rock_idx, rock_idx_adjuster);
-                        self.rocks[rock_idx][x] = rock;
+                        self.rocks[(x, rock_idx)] = rock;
                         span_state = Some(SpanState::Clear);
                         last_rock_idx = Some(rock_idx);
                     }
@@ -133,15 +128,15 @@ impl Platform {
 
     fn slide_horizontal(&mut self, direction: Direction) {
         let (init_rock_idx, rock_idx_adjuster): (usize, fn(usize) -> usize) = match direction {
-            Direction::East => (self.w - 1, |ri| ri - 1),
+            Direction::East => (self.rocks.width() - 1, |ri| ri - 1),
             Direction::West => (0, |ri| ri + 1),
             _ => unreachable!("slide_horizontal only expects horizontal directions"),
         };
-        for y in 0..self.h {
+        for y in 0..self.rocks.height() {
             let mut span_state: Option<SpanState> = None;
             let mut last_rock_idx: Option<usize> = None;
             for x in self.iter_indices(direction) {
-                let cur_rock = self.rocks[y][x];
+                let cur_rock = self.rocks[(x, y)];
                 match (cur_rock, span_state) {
                     (None, None) => {
                         span_state = Some(SpanState::Clear);
@@ -152,9 +147,9 @@ impl Platform {
                     }
                     (None, Some(SpanState::Clear)) => continue,
                     (Some(Rock::Round), Some(SpanState::Clear)) => {
-                        let rock = self.rocks[y][x].take();
+                        let rock = self.rocks[(x, y)].take();
                         let rock_idx = last_rock_idx.map_or(init_rock_idx, rock_idx_adjuster);
-                        self.rocks[y][rock_idx] = rock;
+                        self.rocks[(rock_idx, y)] = rock;
                         span_state = Some(SpanState::Clear);
                         last_rock_idx = Some(rock_idx);
                     }
@@ -175,19 +170,19 @@ impl Platform {
 
     fn iter_indices(&self, direction: Direction) -> Box<dyn Iterator<Item = usize>> {
         match direction {
-            Direction::North => Box::new(0..self.h),
-            Direction::South => Box::new((0..self.h).rev()),
-            Direction::East => Box::new((0..self.w).rev()),
-            Direction::West => Box::new(0..self.w),
+            Direction::North => Box::new(0..self.rocks.height()),
+            Direction::South => Box::new((0..self.rocks.height()).rev()),
+            Direction::East => Box::new((0..self.rocks.width()).rev()),
+            Direction::West => Box::new(0..self.rocks.width()),
         }
     }
 
     pub fn calculate_load(&self) -> usize {
         self.rocks
-            .iter()
+            .rows()
             .enumerate()
             .map(|(y, row)| {
-                let load_per_rock = self.h - y;
+                let load_per_rock = self.rocks.height() - y;
                 row.iter()
                     .filter_map(|rock| match rock {
                         Some(Rock::Round) => Some(load_per_rock),
@@ -201,7 +196,7 @@ impl Platform {
 
 impl Display for Platform {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.rocks.iter() {
+        for row in self.rocks.rows() {
             for maybe_rock in row.iter() {
                 if let Some(rock) = maybe_rock {
                     write!(f, "{}", rock)?;
@@ -228,34 +223,20 @@ impl Display for Rock {
     }
 }
 
-fn parse_rock(input: &str) -> IResult<&str, Option<Rock>> {
-    map(one_of("O#."), |c| match c {
-        'O' => Some(Rock::Round),
-        '#' => Some(Rock::Square),
-        _ => None,
-    })(input)
-}
-
 fn parse_platform(input: &str) -> IResult<&str, Platform> {
-    let rock_row = many1(parse_rock);
-    map(
-        terminated(separated_list1(line_ending, rock_row), opt(line_ending)),
-        |rocks| {
-            let w = rocks[0].len();
-            Platform {
-                w,
-                h: rocks.len(),
-                rocks,
-            }
-        },
-    )(input)
+    map(char_grid(parse_rock), |rocks| Platform { rocks })(input)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+fn parse_rock(c: char) -> Option<Option<Rock>> {
+    match c {
+        'O' => Some(Some(Rock::Round)),
+        '#' => Some(Some(Rock::Square)),
+        '.' => Some(None),
+        _ => None,
+    }
+}
 
-    const INPUT: &str = "O....#....
+const EXAMPLE: &str = "O....#....
 O.OO#....#
 .....##...
 OO.#O....O
@@ -266,15 +247,37 @@ O.#..O.#.#
 #....###..
 #OO..#....";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 136);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 64);
     }
+
+    #[test]
+    fn test_spin_cycle_moves_the_rocks() {
+        let mut platform = parse(EXAMPLE).unwrap();
+        platform.spin_cycle(3);
+        let expected = ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O
+";
+        assert_eq!(platform.to_string(), expected);
+    }
 }
@@ -10,15 +10,27 @@ use nom::{
 };
 
 use crate::parse::{number, parse_all_to};
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let steps = parse_all_to(input, parse_initialization_sequence)?;
+const NAME: &str = "Lens Library";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 15, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 15, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 15, 1, EXAMPLE, 1320));
+    registry.register_example(Example::new(2023, 15, 2, EXAMPLE, 145));
+}
+
+pub fn parse(input: &str) -> Result<Vec<Step>> {
+    parse_all_to(input, parse_initialization_sequence)
+}
+
+pub fn part1(steps: &[Step]) -> Result<usize> {
     let res = steps.iter().map(Step::hash).sum();
     Ok(res)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let steps = parse_all_to(input, parse_initialization_sequence)?;
+pub fn part2(steps: &[Step]) -> Result<usize> {
     let state = steps.iter().fold(LensBoxes::new(), |mut state, step| {
         state.handle(&step.instruction);
         state
@@ -33,7 +45,7 @@ struct LensBoxes {
 }
 
 #[derive(Debug)]
-struct Step {
+pub struct Step {
     hash: usize,
     instruction: Instruction,
 }
@@ -140,21 +152,21 @@ fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
     alt((insert, remove))(input)
 }
 
+const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 1320);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 145);
     }
 }
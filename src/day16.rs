@@ -1,33 +1,49 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fmt::Display,
-};
+use std::{collections::HashSet, fmt::Display};
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, Result};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let mut state = parse_state(input)?;
-    state.energize(Beam(Coord { x: 0, y: 0 }, Direction::Right));
+use crate::algorithm::search;
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::point::Point2;
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "The Floor Will Be Lava";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 16, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 16, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 16, 1, EXAMPLE, 46));
+    registry.register_example(Example::new(2023, 16, 2, EXAMPLE, 51));
+}
+
+pub fn parse(input: &str) -> Result<State> {
+    parse_state(input)
+}
+
+pub fn part1(state: &State) -> Result<usize> {
+    let mut state = state.clone();
+    state.energize(Beam(Coord { x: 0, y: 0 }, Direction::East));
     Ok(state.energized_tile_count())
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let mut state = parse_state(input)?;
-    let w = state.w;
-    let h = state.h;
+pub fn part2(state: &State) -> Result<usize> {
+    let mut state = state.clone();
+    let w = state.entities.width();
+    let h = state.entities.height();
     let max_energized = (0..w)
         // Iterate through beam starting postions at the top and bottom..
         .flat_map(|x| {
             vec![
-                Beam(Coord { x, y: 0 }, Direction::Down),
-                Beam(Coord { x, y: h - 1 }, Direction::Up),
+                Beam(Coord { x, y: 0 }, Direction::South),
+                Beam(Coord { x, y: h - 1 }, Direction::North),
             ]
         })
         // ... and left and right
         .chain((0..h).flat_map(|y| {
             vec![
-                Beam(Coord { x: 0, y }, Direction::Right),
-                Beam(Coord { x: w - 1, y }, Direction::Right),
+                Beam(Coord { x: 0, y }, Direction::East),
+                Beam(Coord { x: w - 1, y }, Direction::East),
             ]
         }))
         // work out how many tiles are energized by each initial beam
@@ -36,22 +52,17 @@ pub fn part2(input: &str) -> Result<usize> {
             state.energized_tile_count()
         })
         .max()
-        .unwrap();
+        .ok_or_else(|| anyhow!("the contraption is empty"))?;
     Ok(max_energized)
 }
 
-struct State {
-    w: usize,
-    h: usize,
-    entities: HashMap<Coord, Entity>,
+#[derive(Clone)]
+pub struct State {
+    entities: Grid<Option<Entity>>,
     energized_tiles: HashSet<Coord>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
+type Coord = Point2<usize>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Entity {
@@ -65,45 +76,32 @@ enum Entity {
     MirrorTRBL,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Beam(Coord, Direction);
 
 impl State {
-    pub fn new(w: usize, h: usize, entities: HashMap<Coord, Entity>) -> Self {
+    fn new(entities: Grid<Option<Entity>>) -> Self {
         Self {
-            w,
-            h,
             entities,
             energized_tiles: HashSet::new(),
         }
     }
 
-    pub fn energize(&mut self, initial_beam: Beam) {
-        self.energized_tiles.clear();
-        let mut seen_beams = HashSet::new();
-        let mut beams_to_process: VecDeque<Beam> = VecDeque::from([initial_beam]);
+    fn energize(&mut self, initial_beam: Beam) {
+        // A beam that starts outside the contraption never energizes anything
+        let start = self
+            .entities
+            .contains(initial_beam.0.into())
+            .then_some(initial_beam);
+        let beams = search::bfs(start, |beam| self.next_beams(*beam));
+        self.energized_tiles = beams.into_keys().map(|Beam(coord, _)| coord).collect();
+    }
 
-        while let Some(beam) = beams_to_process.pop_front() {
-            seen_beams.insert(beam);
-            self.energized_tiles.insert(beam.0);
-            if let Some(entity) = self.entities.get(&beam.0) {
-                self.intersect(beam, *entity)
-                    .into_iter()
-                    .filter(|b| !seen_beams.contains(b))
-                    .for_each(|b| beams_to_process.push_back(b));
-            } else if let Some(new_beam) = self.extend_beam(beam) {
-                if !seen_beams.contains(&new_beam) {
-                    beams_to_process.push_back(new_beam);
-                }
-            }
+    /// Gets the beams that `beam` turns into after passing through its tile
+    fn next_beams(&self, beam: Beam) -> Vec<Beam> {
+        match self.entities.get(beam.0.into()) {
+            Some(Some(entity)) => self.intersect(beam, *entity),
+            _ => self.extend_beam(beam).into_iter().collect(),
         }
     }
 
@@ -112,114 +110,48 @@ impl State {
     }
 
     fn intersect(&self, beam: Beam, entity: Entity) -> Vec<Beam> {
-        let mut new_beams = Vec::new();
-        let Beam(Coord { x, y }, beam_direction) = beam;
-        match (entity, beam_direction) {
-            // |
-            (Entity::SplitterVertical, Direction::Up) => {
-                if y > 0 {
-                    new_beams.push(Beam(Coord { x, y: y - 1 }, Direction::Up));
-                }
+        let Beam(coord, direction) = beam;
+        let directions = match entity {
+            Entity::SplitterVertical if direction.is_horizontal() => {
+                vec![Direction::North, Direction::South]
             }
-            (Entity::SplitterVertical, Direction::Down) => {
-                if y < self.h - 1 {
-                    new_beams.push(Beam(Coord { x, y: y + 1 }, Direction::Down));
-                }
-            }
-            (Entity::SplitterVertical, Direction::Left | Direction::Right) => {
-                if y > 0 {
-                    new_beams.push(Beam(Coord { x, y: y - 1 }, Direction::Up));
-                }
-                if y < self.h - 1 {
-                    new_beams.push(Beam(Coord { x, y: y + 1 }, Direction::Down));
-                }
-            }
-            // -
-            (Entity::SplitterHorizontal, Direction::Up | Direction::Down) => {
-                if x > 0 {
-                    new_beams.push(Beam(Coord { x: x - 1, y }, Direction::Left));
-                }
-                if x < self.w - 1 {
-                    new_beams.push(Beam(Coord { x: x + 1, y }, Direction::Right));
-                }
-            }
-            (Entity::SplitterHorizontal, Direction::Left) => {
-                if x > 0 {
-                    new_beams.push(Beam(Coord { x: x - 1, y }, Direction::Left));
-                }
-            }
-            (Entity::SplitterHorizontal, Direction::Right) => {
-                if x < self.w - 1 {
-                    new_beams.push(Beam(Coord { x: x + 1, y }, Direction::Right));
-                }
+            Entity::SplitterHorizontal if direction.is_vertical() => {
+                vec![Direction::East, Direction::West]
             }
+            Entity::SplitterVertical | Entity::SplitterHorizontal => vec![direction],
             // \
-            (Entity::MirrorTLBR, Direction::Up) => {
-                if x > 0 {
-                    new_beams.push(Beam(Coord { x: x - 1, y }, Direction::Left));
-                }
-            }
-            (Entity::MirrorTLBR, Direction::Down) => {
-                if x < self.w - 1 {
-                    new_beams.push(Beam(Coord { x: x + 1, y }, Direction::Right));
-                }
-            }
-            (Entity::MirrorTLBR, Direction::Left) => {
-                if y > 0 {
-                    new_beams.push(Beam(Coord { x, y: y - 1 }, Direction::Up));
-                }
-            }
-            (Entity::MirrorTLBR, Direction::Right) => {
-                if y < self.h - 1 {
-                    new_beams.push(Beam(Coord { x, y: y + 1 }, Direction::Down));
-                }
-            }
+            Entity::MirrorTLBR if direction.is_vertical() => vec![direction.turn_left()],
+            Entity::MirrorTLBR => vec![direction.turn_right()],
             // /
-            (Entity::MirrorTRBL, Direction::Up) => {
-                if x < self.w - 1 {
-                    new_beams.push(Beam(Coord { x: x + 1, y }, Direction::Right));
-                }
-            }
-            (Entity::MirrorTRBL, Direction::Down) => {
-                if x > 0 {
-                    new_beams.push(Beam(Coord { x: x - 1, y }, Direction::Left));
-                }
-            }
-            (Entity::MirrorTRBL, Direction::Left) => {
-                if y < self.h - 1 {
-                    new_beams.push(Beam(Coord { x, y: y + 1 }, Direction::Down));
-                }
-            }
-            (Entity::MirrorTRBL, Direction::Right) => {
-                if y > 0 {
-                    new_beams.push(Beam(Coord { x, y: y - 1 }, Direction::Up));
-                }
-            }
-        }
-        new_beams
+            Entity::MirrorTRBL if direction.is_vertical() => vec![direction.turn_right()],
+            Entity::MirrorTRBL => vec![direction.turn_left()],
+        };
+        directions
+            .into_iter()
+            .filter_map(|d| self.move_beam(coord, d))
+            .collect()
     }
 
     fn extend_beam(&self, beam: Beam) -> Option<Beam> {
-        let Beam(Coord { x, y }, beam_direction) = beam;
-        match beam_direction {
-            Direction::Up if y > 0 => Some(Beam(Coord { x, y: y - 1 }, Direction::Up)),
-            Direction::Down if y < self.h - 1 => Some(Beam(Coord { x, y: y + 1 }, Direction::Down)),
-            Direction::Left if x > 0 => Some(Beam(Coord { x: x - 1, y }, Direction::Left)),
-            Direction::Right if x < self.h - 1 => {
-                Some(Beam(Coord { x: x + 1, y }, Direction::Right))
-            }
-            _ => None,
-        }
+        let Beam(coord, direction) = beam;
+        self.move_beam(coord, direction)
+    }
+
+    /// Moves a beam one tile from `coord` in `direction`, unless that would
+    /// take it out of the contraption
+    fn move_beam(&self, coord: Coord, direction: Direction) -> Option<Beam> {
+        let next = self.entities.step(coord.into(), direction)?;
+        Some(Beam(next.into(), direction))
     }
 }
 
 impl Display for State {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.h {
-            for x in 0..self.w {
+        for y in 0..self.entities.height() {
+            for x in 0..self.entities.width() {
                 if self.energized_tiles.contains(&Coord { x, y }) {
                     write!(f, "#")?;
-                } else if let Some(entity) = self.entities.get(&Coord { x, y }) {
+                } else if let Some(entity) = self.entities[(x, y)] {
                     write!(f, "{}", entity)?;
                 } else {
                     write!(f, ".")?;
@@ -245,46 +177,27 @@ impl Display for Entity {
 
 impl Display for Beam {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let arrow = match self.1 {
-            Direction::Up => '↑',
-            Direction::Down => '↓',
-            Direction::Left => '←',
-            Direction::Right => '→',
-        };
-        write!(f, "{} ({}, {})", arrow, self.0.x, self.0.y)
+        write!(f, "{} ({}, {})", self.1, self.0.x, self.0.y)
     }
 }
 
 fn parse_state(input: &str) -> Result<State> {
-    let mut h = 0;
-    let mut w = 0;
-    let mut entities = HashMap::new();
-    for (y, row) in input.lines().enumerate() {
-        h += 1;
-        w = 0;
-        for (x, c) in row.chars().enumerate() {
-            w += 1;
-            if c == '.' {
-                continue;
-            }
-            let entity = match c {
-                '|' => Entity::SplitterVertical,
-                '-' => Entity::SplitterHorizontal,
-                '\\' => Entity::MirrorTLBR,
-                '/' => Entity::MirrorTRBL,
-                _ => bail!("Unexpected character: {}", c),
-            };
-            entities.insert(Coord { x, y }, entity);
-        }
-    }
-    Ok(State::new(w, h, entities))
+    let entities = Grid::parse(
+        input,
+        |c| match c {
+            '.' => Some(None),
+            '|' => Some(Some(Entity::SplitterVertical)),
+            '-' => Some(Some(Entity::SplitterHorizontal)),
+            '\\' => Some(Some(Entity::MirrorTLBR)),
+            '/' => Some(Some(Entity::MirrorTRBL)),
+            _ => None,
+        },
+        "a mirror, a splitter or '.'",
+    )?;
+    Ok(State::new(entities))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = r".|...\....
+const EXAMPLE: &str = r".|...\....
 |.-.\.....
 .....|-...
 ........|.
@@ -295,15 +208,26 @@ mod test {
 .|....-|.\
 ..//.|....";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 46);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 51);
     }
+
+    #[test]
+    fn test_an_empty_contraption_energizes_nothing() {
+        let state = parse("").unwrap();
+        assert_eq!(part1(&state).unwrap(), 0);
+        assert!(part2(&state).is_err());
+    }
 }
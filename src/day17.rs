@@ -1,34 +1,53 @@
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashMap, HashSet},
-};
+use anyhow::{anyhow, bail, Result};
 
-use anyhow::{anyhow, Result};
+use crate::algorithm::shortest_path::dijkstra;
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::point::Point2;
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let costs = parse_costs(input)?;
+const NAME: &str = "Clumsy Crucible";
 
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 17, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 17, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 17, 1, EXAMPLE, 102));
+    registry.register_example(Example::new(2023, 17, 2, EXAMPLE, 94));
+}
+
+/// The heat lost by entering each block in the city
+pub type Costs = Grid<usize>;
+
+pub fn parse(input: &str) -> Result<Costs> {
+    let costs = Grid::parse(input, |c| c.to_digit(10).map(|d| d as usize), "a digit")?;
+    if costs.width() == 0 || costs.height() == 0 {
+        bail!("the city is empty");
+    }
+    Ok(costs)
+}
+
+pub fn part1(costs: &Costs) -> Result<usize> {
     let get_neighbours = |vertex: &Vertex| -> Vec<Vertex> {
         let poss_new = if vertex.forward_count < 3 {
             vec![
-                vertex.forward(&costs),
-                vertex.left(&costs),
-                vertex.right(&costs),
+                vertex.forward(costs),
+                vertex.left(costs),
+                vertex.right(costs),
             ]
         } else {
-            vec![vertex.left(&costs), vertex.right(&costs)]
+            vec![vertex.left(costs), vertex.right(costs)]
         };
         poss_new.iter().filter_map(|v| *v).collect()
     };
 
-    let get_cost = |to: &Vertex| -> usize {
-        let Coord(x, y) = to.pos.1;
-        costs.values[y][x]
+    let get_cost = |_from: &Vertex, to: &Vertex| -> usize {
+        let Coord { x, y } = to.pos.1;
+        costs[(x, y)]
     };
 
     let is_dest = |vertex: &Vertex| -> bool {
-        let Coord(x, y) = vertex.pos.1;
-        x == costs.w - 1 && y == costs.h - 1
+        let Coord { x, y } = vertex.pos.1;
+        x == costs.width() - 1 && y == costs.height() - 1
     };
 
     let starts = vec![
@@ -36,39 +55,39 @@ pub fn part1(input: &str) -> Result<usize> {
         Vertex::new(Direction::South, 0, 0, 0),
     ];
 
-    dijkstra(starts, &get_neighbours, &get_cost, &is_dest).ok_or(anyhow!("could not find path"))
+    dijkstra(starts, get_neighbours, get_cost, is_dest)
+        .map(|path| path.cost)
+        .ok_or(anyhow!("could not find path"))
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let costs = parse_costs(input)?;
-
+pub fn part2(costs: &Costs) -> Result<usize> {
     let get_neighbours = |vertex: &Vertex| -> Vec<Vertex> {
         // Once an ultra crucible starts moving in a direction, it needs to move a minimum of four blocks in
         // that direction before it can turn
         let poss_new = if vertex.forward_count < 4 {
-            vec![vertex.forward(&costs)]
+            vec![vertex.forward(costs)]
         } else if vertex.forward_count == 10 {
             // an ultra crucible can move a maximum of ten consecutive blocks without turning
-            vec![vertex.left(&costs), vertex.right(&costs)]
+            vec![vertex.left(costs), vertex.right(costs)]
         } else {
             vec![
-                vertex.forward(&costs),
-                vertex.left(&costs),
-                vertex.right(&costs),
+                vertex.forward(costs),
+                vertex.left(costs),
+                vertex.right(costs),
             ]
         };
         poss_new.iter().filter_map(|v| *v).collect()
     };
 
-    let get_cost = |to: &Vertex| -> usize {
-        let Coord(x, y) = to.pos.1;
-        costs.values[y][x]
+    let get_cost = |_from: &Vertex, to: &Vertex| -> usize {
+        let Coord { x, y } = to.pos.1;
+        costs[(x, y)]
     };
 
     let is_dest = |vertex: &Vertex| -> bool {
-        let Coord(x, y) = vertex.pos.1;
+        let Coord { x, y } = vertex.pos.1;
         // has to have moved at least 4 blocks in a straight line before it can stop
-        x == costs.w - 1 && y == costs.h - 1 && vertex.forward_count >= 4
+        x == costs.width() - 1 && y == costs.height() - 1 && vertex.forward_count >= 4
     };
 
     let starts = vec![
@@ -76,90 +95,12 @@ pub fn part2(input: &str) -> Result<usize> {
         Vertex::new(Direction::South, 0, 0, 0),
     ];
 
-    dijkstra(starts, &get_neighbours, &get_cost, &is_dest).ok_or(anyhow!("could not find path"))
-}
-
-fn parse_costs(input: &str) -> Result<Costs> {
-    let mut h = 0;
-    let mut w = 0;
-    let mut rows = Vec::new();
-    for line in input.lines() {
-        let mut row = Vec::new();
-        h += 1;
-        w = 0;
-        for c in line.chars() {
-            w += 1;
-            let val = c.to_digit(10).ok_or(anyhow!("parsing failed"))? as usize;
-            row.push(val);
-        }
-        rows.push(row);
-    }
-    Ok(Costs { w, h, values: rows })
+    dijkstra(starts, get_neighbours, get_cost, is_dest)
+        .map(|path| path.cost)
+        .ok_or(anyhow!("could not find path"))
 }
 
-fn dijkstra(
-    starts: Vec<Vertex>,
-    get_neighbours: &dyn Fn(&Vertex) -> Vec<Vertex>,
-    get_cost: &dyn Fn(&Vertex) -> usize,
-    is_dest: &dyn Fn(&Vertex) -> bool,
-) -> Option<usize> {
-    let mut distances = HashMap::new();
-    let mut to_visit = BinaryHeap::new();
-    let mut visited = HashSet::new();
-
-    for start in starts.into_iter() {
-        distances.insert(start, 0);
-        to_visit.push(Reverse(Visit {
-            vertex: start,
-            distance: 0,
-        }));
-    }
-
-    while let Some(Reverse(Visit { vertex, distance })) = to_visit.pop() {
-        if !visited.insert(vertex) {
-            continue;
-        }
-
-        if is_dest(&vertex) {
-            return Some(distance);
-        }
-
-        for neighbour in get_neighbours(&vertex) {
-            let cost = get_cost(&neighbour);
-            let new_distance = distance + cost;
-            let is_closer = distances
-                .get(&neighbour)
-                .map_or(true, |prev| new_distance < *prev);
-
-            if is_closer {
-                distances.insert(neighbour, new_distance);
-                to_visit.push(Reverse(Visit {
-                    vertex: neighbour,
-                    distance: new_distance,
-                }));
-            }
-        }
-    }
-
-    None
-}
-
-struct Costs {
-    w: usize,
-    h: usize,
-    values: Vec<Vec<usize>>,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord(usize, usize);
+type Coord = Point2<usize>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Vector(Direction, Coord);
@@ -170,95 +111,44 @@ struct Vertex {
     forward_count: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Visit {
-    vertex: Vertex,
-    distance: usize,
-}
-
 impl Vertex {
     pub fn new(d: Direction, x: usize, y: usize, forward_count: usize) -> Self {
         Self {
-            pos: Vector(d, Coord(x, y)),
+            pos: Vector(d, Coord { x, y }),
             forward_count,
         }
     }
 
     pub fn forward(&self, grid: &Costs) -> Option<Self> {
         let Self {
-            pos: Vector(direction, coord),
+            pos: Vector(direction, Coord { x, y }),
             forward_count,
         } = *self;
-        let Coord(x, y) = coord;
-
-        let new_coord = match direction {
-            Direction::North if y > 0 => Some(Coord(x, y - 1)),
-            Direction::East if x < grid.w - 1 => Some(Coord(x + 1, y)),
-            Direction::South if y < grid.h - 1 => Some(Coord(x, y + 1)),
-            Direction::West if x > 0 => Some(Coord(x - 1, y)),
-            _ => None,
-        };
 
-        new_coord.map(|coord| Self {
-            pos: Vector(direction, coord),
+        grid.step((x, y), direction).map(|(x, y)| Self {
+            pos: Vector(direction, Coord { x, y }),
             forward_count: forward_count + 1,
         })
     }
 
     pub fn left(&self, grid: &Costs) -> Option<Self> {
-        let Self {
-            pos: Vector(direction, coord),
-            forward_count: _,
-        } = *self;
-        let new_direction = match direction {
-            Direction::North => Direction::West,
-            Direction::East => Direction::North,
-            Direction::South => Direction::East,
-            Direction::West => Direction::South,
-        };
-        Self {
-            pos: Vector(new_direction, coord),
-            forward_count: 0,
-        }
-        .forward(grid)
+        self.turn(self.pos.0.turn_left()).forward(grid)
     }
 
     pub fn right(&self, grid: &Costs) -> Option<Self> {
-        let Self {
-            pos: Vector(direction, coord),
-            forward_count: _,
-        } = *self;
-        let new_direction = match direction {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        };
+        self.turn(self.pos.0.turn_right()).forward(grid)
+    }
+
+    /// Faces `direction` without moving, which resets the forward count
+    fn turn(&self, direction: Direction) -> Self {
         Self {
-            pos: Vector(new_direction, coord),
+            pos: Vector(direction, self.pos.1),
             forward_count: 0,
         }
-        .forward(grid)
     }
 }
 
-impl Ord for Visit {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.distance.cmp(&other.distance)
-    }
-}
-
-impl PartialOrd for Visit {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "2413432311323
+const EXAMPLE: &str = "2413432311323
 3215453535623
 3255245654254
 3446585845452
@@ -272,6 +162,10 @@ mod test {
 2546548887735
 4322674655533";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     const INPUT2: &str = "111111111111
 999999999991
 999999999991
@@ -280,15 +174,20 @@ mod test {
 
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 102);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let cases = vec![(INPUT, 94), (INPUT2, 71)];
+        let cases = vec![(EXAMPLE, 94), (INPUT2, 71)];
         for (input, expect) in cases {
-            assert_eq!(part2(input).unwrap(), expect);
+            assert_eq!(part2(&parse(input).unwrap()).unwrap(), expect);
         }
     }
+
+    #[test]
+    fn test_an_empty_city_is_an_error() {
+        assert!(parse("").is_err());
+    }
 }
@@ -1,65 +1,74 @@
 use anyhow::Result;
 use nom::{
-    bytes::complete::{tag, take_while_m_n},
-    character::complete::{alphanumeric1, char, one_of},
+    character::complete::{char, one_of},
     combinator::{map, map_res},
-    sequence::{delimited, separated_pair, terminated, tuple},
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
 use crate::{
     algorithm::shoelace,
-    parse::{number, parse_lines_to_vec},
-    point::Point,
+    direction::Direction,
+    parse::{hex_colour, number, parse_lines_to_vec},
+    point::Point2,
+    solver::{Example, FnSolver, Registry},
 };
 
-pub fn part1(input: &str) -> Result<usize> {
-    solve(input, ParseMode::Part1)
+const NAME: &str = "Lavaduct Lagoon";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 18, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 18, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 18, 1, EXAMPLE, 62));
+    registry.register_example(Example::new(2023, 18, 2, EXAMPLE, 952408144115usize));
+}
+
+pub fn parse(input: &str) -> Result<DigPlan> {
+    Ok(DigPlan {
+        instructions: parse_lines_to_vec(input, parse_part1_instruction)?,
+        colour_instructions: parse_lines_to_vec(input, parse_part2_instruction)?,
+    })
+}
+
+pub fn part1(plan: &DigPlan) -> Result<usize> {
+    Ok(solve(&plan.instructions))
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    // I'm sure there's a more optimal way to solve this,
-    // but brute force works in a second or so, so... ¯\_(ツ)_/¯
-    solve(input, ParseMode::Part2)
+pub fn part2(plan: &DigPlan) -> Result<usize> {
+    Ok(solve(&plan.colour_instructions))
 }
 
-fn solve(input: &str, parse_mode: ParseMode) -> Result<usize> {
-    let parse_fn = match parse_mode {
-        ParseMode::Part1 => parse_part1_instruction,
-        ParseMode::Part2 => parse_part2_instruction,
-    };
-    let instructions = parse_lines_to_vec(input, parse_fn)?;
-    let coords = apply_instructions(&instructions);
-    let enclosed_point_count = shoelace::enclosed_area(&coords);
-    Ok(coords.len() + enclosed_point_count as usize - 1)
+fn solve(instructions: &[Instruction]) -> usize {
+    let corners = apply_instructions(instructions);
+    let perimeter = instructions.iter().map(|i| i.length as i64).sum();
+    shoelace::lattice_count(&corners, perimeter).total as usize
 }
 
-/// Starting at (0,0) applies the given `instructions` to find all of the trench coordinates.
+/// The dig plan, read as the plain instructions for part 1 and as the
+/// instructions hidden in the colour codes for part 2.
+#[derive(Debug)]
+pub struct DigPlan {
+    instructions: Vec<Instruction>,
+    colour_instructions: Vec<Instruction>,
+}
+
+/// Starting at (0,0) applies the given `instructions` to find the corners of the trench.
 /// (0,0) should end up in the returned list twice (at the start and the end)
 fn apply_instructions(instructions: &[Instruction]) -> Vec<Coord> {
-    let mut pos = Coord(0, 0);
+    let mut pos = Coord::new(0, 0);
     vec![pos]
         .into_iter()
-        .chain(instructions.iter().flat_map(|instruction| {
-            (0..instruction.length)
-                .map(|_| {
-                    pos = instruction.apply_to(pos);
-                    pos
-                })
-                .collect::<Vec<_>>()
+        .chain(instructions.iter().map(|instruction| {
+            pos = instruction.apply_to(pos);
+            pos
         }))
         .collect()
 }
 
 impl Instruction {
     fn apply_to(&self, coord: Coord) -> Coord {
-        let Coord(x, y) = coord;
-        match self.dir {
-            Direction::Up => Coord(x, y - 1),
-            Direction::Down => Coord(x, y + 1),
-            Direction::Left => Coord(x - 1, y),
-            Direction::Right => Coord(x + 1, y),
-        }
+        let (dx, dy) = self.dir.delta();
+        coord + Coord::new(dx as i64, dy as i64) * self.length as i64
     }
 }
 
@@ -69,27 +78,11 @@ struct Instruction {
     length: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Coord(i64, i64);
-
-enum ParseMode {
-    Part1,
-    Part2,
-}
+type Coord = Point2<i64>;
 
 fn parse_part1_instruction(input: &str) -> IResult<&str, Instruction> {
-    let parse_direction = map_res(one_of("UDLR"), |c| {
-        Direction::try_from((ParseMode::Part1, c))
-    });
-    let parse_colour = delimited(tag("(#"), alphanumeric1, char(')'));
+    let parse_direction = map_res(one_of("UDLR"), Direction::try_from);
+    let parse_colour = delimited(char('('), hex_colour, char(')'));
 
     map(
         tuple((
@@ -103,76 +96,35 @@ fn parse_part1_instruction(input: &str) -> IResult<&str, Instruction> {
 
 fn parse_part2_instruction(input: &str) -> IResult<&str, Instruction> {
     // L 10 (#3e6430)
-    // Parsers for the "L 10 (#" that we need to ignore
+    // Parsers for the "L 10 " that we need to ignore
     let ignore_dir = one_of("UDLR");
-    let ignore_num = terminated(number, tag(" (#"));
+    let ignore_num = terminated(number, char(' '));
     let line_start = separated_pair(ignore_dir, char(' '), ignore_num);
+    let parse_colour = delimited(char('('), hex_colour, char(')'));
 
     // The first five hexadecimal digits encode the distance in meters as a five-digit
     // hexadecimal number. The last hexadecimal digit encodes the direction to dig.
-    let parse_length = map_res(take_while_m_n(5, 5, is_hex_digit), from_hex);
-    let parse_direction = map_res(one_of("0123"), |c| {
-        Direction::try_from((ParseMode::Part2, c))
-    });
-
-    map(
-        tuple((
-            line_start,
-            parse_length,
-            terminated(parse_direction, char(')')),
-        )),
-        |(_ignore, length, dir)| Instruction { dir, length },
-    )(input)
+    map_res(preceded(line_start, parse_colour), |colour| {
+        Ok::<_, String>(Instruction {
+            dir: direction_from_digit(colour & 0xf)?,
+            length: (colour >> 4) as usize,
+        })
+    })(input)
 }
 
-fn is_hex_digit(c: char) -> bool {
-    c.is_ascii_hexdigit()
-}
-
-fn from_hex(input: &str) -> Result<usize, std::num::ParseIntError> {
-    usize::from_str_radix(input, 16)
-}
-
-impl TryFrom<(ParseMode, char)> for Direction {
-    type Error = String;
-
-    fn try_from(value: (ParseMode, char)) -> std::result::Result<Self, Self::Error> {
-        let (parse_mode, value) = value;
-        match parse_mode {
-            ParseMode::Part1 => match value {
-                'U' => Ok(Self::Up),
-                'D' => Ok(Self::Down),
-                'L' => Ok(Self::Left),
-                'R' => Ok(Self::Right),
-                value => Err(format!("invalid direction: {}", value)),
-            },
-            // 0 means R, 1 means D, 2 means L, and 3 means U
-            ParseMode::Part2 => match value {
-                '0' => Ok(Self::Right),
-                '1' => Ok(Self::Down),
-                '2' => Ok(Self::Left),
-                '3' => Ok(Self::Up),
-                value => Err(format!("invalid direction: {}", value)),
-            },
-        }
-    }
-}
-
-impl Point<i64> for Coord {
-    fn x(&self) -> i64 {
-        self.0
-    }
-
-    fn y(&self) -> i64 {
-        self.1
+/// Reads the direction from the last digit of a colour code
+fn direction_from_digit(value: u32) -> std::result::Result<Direction, String> {
+    // 0 means R, 1 means D, 2 means L, and 3 means U
+    match value {
+        0 => Ok(Direction::East),
+        1 => Ok(Direction::South),
+        2 => Ok(Direction::West),
+        3 => Ok(Direction::North),
+        value => Err(format!("invalid direction: {:x}", value)),
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "R 6 (#70c710)
+const EXAMPLE: &str = "R 6 (#70c710)
 D 5 (#0dc571)
 L 2 (#5713f0)
 D 2 (#d2c081)
@@ -187,15 +139,19 @@ U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 62);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 952408144115);
     }
 }
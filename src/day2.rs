@@ -1,18 +1,29 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, line_ending};
-use nom::combinator::{all_consuming, map, opt};
+use nom::character::complete::char;
+use nom::combinator::map;
 use nom::multi::separated_list1;
-use nom::sequence::{preceded, separated_pair, terminated};
+use nom::sequence::{preceded, separated_pair};
 use nom::IResult;
 
-use crate::parse::number;
+use crate::parse::{number, parse_lines_to_vec};
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let Ok((_, games)) = parse_games(input) else {
-        bail!("could not parse input");
-    };
+const NAME: &str = "Cube Conundrum";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 2, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 2, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 2, 1, EXAMPLE, 8));
+    registry.register_example(Example::new(2023, 2, 2, EXAMPLE, 2286));
+}
+
+pub fn parse(input: &str) -> Result<Vec<Game>> {
+    parse_lines_to_vec(input, parse_game)
+}
+
+pub fn part1(games: &[Game]) -> Result<usize> {
     let res = games
         .iter()
         .filter(|g| g.all_reveals_less_than(13, 14, 15))
@@ -21,10 +32,7 @@ pub fn part1(input: &str) -> Result<usize> {
     Ok(res)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let Ok((_, games)) = parse_games(input) else {
-        bail!("could not parse input");
-    };
+pub fn part2(games: &[Game]) -> Result<usize> {
     let res = games
         .iter()
         .map(|gm| {
@@ -36,7 +44,7 @@ pub fn part2(input: &str) -> Result<usize> {
 }
 
 #[derive(Debug, PartialEq)]
-struct Game {
+pub struct Game {
     id: usize,
     reveals: Vec<Reveal>,
 }
@@ -74,7 +82,7 @@ impl Game {
 }
 
 #[derive(Debug, PartialEq)]
-struct Reveal {
+pub struct Reveal {
     r: Option<usize>,
     g: Option<usize>,
     b: Option<usize>,
@@ -133,32 +141,26 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     )(input)
 }
 
-fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
-    all_consuming(terminated(
-        separated_list1(line_ending, parse_game),
-        opt(line_ending),
-    ))(input)
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT1: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseError;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT1).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 8);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT1).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 2286);
     }
 
@@ -167,47 +169,53 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         let input = "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red";
         assert_eq!(
-            parse_games(input),
-            Ok((
-                "",
-                vec![
-                    Game {
-                        id: 4,
-                        reveals: vec![
-                            Reveal {
-                                r: Some(3),
-                                g: Some(1),
-                                b: Some(6)
-                            },
-                            Reveal {
-                                r: Some(6),
-                                g: Some(3),
-                                b: None,
-                            },
-                            Reveal {
-                                r: Some(14),
-                                g: Some(3),
-                                b: Some(15)
-                            },
-                        ]
-                    },
-                    Game {
-                        id: 5,
-                        reveals: vec![
-                            Reveal {
-                                r: Some(6),
-                                g: Some(3),
-                                b: Some(1)
-                            },
-                            Reveal {
-                                r: Some(1),
-                                g: None,
-                                b: Some(2)
-                            }
-                        ]
-                    }
-                ]
-            ))
+            parse(input).unwrap(),
+            vec![
+                Game {
+                    id: 4,
+                    reveals: vec![
+                        Reveal {
+                            r: Some(3),
+                            g: Some(1),
+                            b: Some(6)
+                        },
+                        Reveal {
+                            r: Some(6),
+                            g: Some(3),
+                            b: None,
+                        },
+                        Reveal {
+                            r: Some(14),
+                            g: Some(3),
+                            b: Some(15)
+                        },
+                    ]
+                },
+                Game {
+                    id: 5,
+                    reveals: vec![
+                        Reveal {
+                            r: Some(6),
+                            g: Some(3),
+                            b: Some(1)
+                        },
+                        Reveal {
+                            r: Some(1),
+                            g: None,
+                            b: Some(2)
+                        }
+                    ]
+                }
+            ]
         );
     }
+
+    #[test]
+    fn test_parse_errors_point_at_the_problem() {
+        let input = "Game 1: 3 blue, 4 red
+Game 2: 1 blue, 2 purple";
+        let err = parse(input).unwrap_err().downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 15));
+        assert_eq!(err.expected, "a line ending");
+    }
 }
@@ -1,37 +1,53 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::collections::{HashMap, HashSet};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let grid = Grid::from(input);
-    let numbers: Vec<usize> = grid
+use crate::grid::Grid;
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "Gear Ratios";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 3, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 3, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 3, 1, EXAMPLE, 4361));
+    registry.register_example(Example::new(2023, 3, 2, EXAMPLE, 467835));
+}
+
+pub fn parse(input: &str) -> Result<Schematic> {
+    let grid = Grid::parse(input, |c| Some(Cell::from(c)), "any character")?;
+    Ok(Schematic { grid })
+}
+
+pub fn part1(schematic: &Schematic) -> Result<usize> {
+    let numbers: Vec<usize> = schematic
         .parse_numbers()
         .into_iter()
-        .filter(|num| num.has_adjacent_symbol(&grid))
-        .map(|n| n.into())
-        .collect();
+        .filter(|num| num.has_adjacent_symbol(schematic))
+        .map(|n| n.value())
+        .collect::<Result<_>>()?;
     Ok(numbers.iter().sum())
 }
 
-pub fn part2(input: &str) -> Result<usize> {
+pub fn part2(schematic: &Schematic) -> Result<usize> {
     // Get numbers
-    let grid = Grid::from(input);
-    let numbers: Vec<Number> = grid.parse_numbers();
+    let numbers: Vec<Number> = schematic.parse_numbers();
 
     // Make a map of coord -> number
-    let num_coords: HashMap<Coord, usize> = numbers.iter().fold(HashMap::new(), |mut acc, n| {
-        let n_usize: usize = n.into();
+    let mut num_coords: HashMap<Coord, usize> = HashMap::new();
+    for n in numbers.iter() {
+        let n_usize = n.value()?;
         for c in n.num_coords.iter() {
-            acc.insert(*c, n_usize);
+            num_coords.insert(*c, n_usize);
         }
-        acc
-    });
+    }
     // Get gear coords
     // For each gear, make set of numbers in adjacent cells, if list len is 2 keep and multiply members
-    let sum: usize = grid
+    let sum: usize = schematic
         .parse_gear_coords()
         .iter()
         .map(|c| {
-            grid.adjacent_coords(*c)
+            schematic
+                .adjacent_coords(*c)
                 .iter()
                 .filter_map(|c| num_coords.get(c).cloned())
                 .collect::<HashSet<usize>>()
@@ -45,14 +61,12 @@ pub fn part2(input: &str) -> Result<usize> {
 type Coord = (usize, usize);
 
 #[derive(Debug)]
-struct Grid {
-    w: usize,
-    h: usize,
-    rows: Vec<Vec<Cell>>,
+pub struct Schematic {
+    grid: Grid<Cell>,
 }
 
 #[derive(Debug, PartialEq)]
-enum Cell {
+pub enum Cell {
     Symbol,
     Gear,
     Empty,
@@ -88,38 +102,33 @@ impl Number {
         self
     }
 
-    pub fn has_adjacent_symbol(&self, grid: &Grid) -> bool {
-        self.adjacent_coords
-            .iter()
-            .any(|coord| grid.is_symbol(*coord))
-    }
-}
-
-impl From<Number> for usize {
-    fn from(value: Number) -> Self {
-        value.digits.parse().unwrap()
+    /// Gets the number's value, which fails if it's too big for a `usize`
+    pub fn value(&self) -> Result<usize> {
+        self.digits
+            .parse()
+            .with_context(|| format!("the number {} is too big", self.digits))
     }
-}
 
-impl From<&Number> for usize {
-    fn from(value: &Number) -> Self {
-        value.digits.parse().unwrap()
+    pub fn has_adjacent_symbol(&self, schematic: &Schematic) -> bool {
+        self.adjacent_coords
+            .iter()
+            .any(|coord| schematic.is_symbol(*coord))
     }
 }
 
-impl Grid {
-    pub fn parse_numbers(&self) -> Vec<Number> {
+impl Schematic {
+    fn parse_numbers(&self) -> Vec<Number> {
         let mut numbers = Vec::new();
         let mut curr_num: Option<Number> = None;
-        for (y, row) in self.rows.iter().enumerate() {
-            if curr_num.is_some() {
-                numbers.push(curr_num.take().unwrap());
+        for (y, row) in self.grid.rows().enumerate() {
+            if let Some(num) = curr_num.take() {
+                numbers.push(num);
             }
             for (x, cell) in row.iter().enumerate() {
                 match cell {
                     Cell::Gear | Cell::Symbol | Cell::Empty => {
-                        if curr_num.is_some() {
-                            numbers.push(curr_num.take().unwrap());
+                        if let Some(num) = curr_num.take() {
+                            numbers.push(num);
                         }
                     }
                     Cell::Digit(c) => {
@@ -135,82 +144,19 @@ impl Grid {
     }
 
     pub fn parse_gear_coords(&self) -> Vec<Coord> {
-        let mut gear_coords = Vec::new();
-        for (y, row) in self.rows.iter().enumerate() {
-            for (x, cell) in row.iter().enumerate() {
-                if Cell::Gear == *cell {
-                    gear_coords.push((x, y));
-                }
-            }
-        }
-        gear_coords
+        self.grid
+            .iter()
+            .filter(|(_, cell)| **cell == Cell::Gear)
+            .map(|(coord, _)| coord)
+            .collect()
     }
 
-    pub fn is_symbol(&self, (x, y): Coord) -> bool {
-        let Some(row) = self.rows.get(y) else {
-            return false;
-        };
-        let Some(cell) = row.get(x) else {
-            return false;
-        };
-        matches!(cell, Cell::Gear | Cell::Symbol)
+    pub fn is_symbol(&self, coord: Coord) -> bool {
+        matches!(self.grid.get(coord), Some(Cell::Gear | Cell::Symbol))
     }
 
-    pub fn adjacent_coords(&self, (x, y): Coord) -> HashSet<Coord> {
-        let mut adj = HashSet::new();
-        // TL
-        if x > 0 && y > 0 {
-            adj.insert((x - 1, y - 1));
-        }
-        // T
-        if y > 0 {
-            adj.insert((x, y - 1));
-        }
-        // TR
-        if x < self.w - 1 && y > 0 {
-            adj.insert((x + 1, y - 1));
-        }
-        // L
-        if x > 0 {
-            adj.insert((x - 1, y));
-        }
-        // R
-        if x < self.w - 1 {
-            adj.insert((x + 1, y));
-        }
-        // BL
-        if x > 0 && y < self.h - 1 {
-            adj.insert((x - 1, y + 1));
-        }
-        // B
-        if y < self.h - 1 {
-            adj.insert((x, y + 1));
-        }
-        // BR
-        if x < self.w - 1 && y < self.h - 1 {
-            adj.insert((x + 1, y + 1));
-        }
-        adj
-    }
-}
-
-impl From<&str> for Grid {
-    fn from(value: &str) -> Self {
-        let mut w = 0;
-        let mut rows = Vec::new();
-        for line in value.lines() {
-            let cols: Vec<Cell> = line.chars().map(|c| c.into()).collect();
-            if w == 0 {
-                w = cols.len();
-            }
-            rows.push(cols);
-        }
-
-        Grid {
-            w,
-            h: rows.len(),
-            rows,
-        }
+    pub fn adjacent_coords(&self, coord: Coord) -> HashSet<Coord> {
+        self.grid.neighbours8(coord).collect()
     }
 }
 
@@ -227,11 +173,7 @@ impl From<char> for Cell {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "467..114..
+const EXAMPLE: &str = "467..114..
 ...*......
 ..35..633.
 ......#...
@@ -242,15 +184,19 @@ mod test {
 ...$.*....
 .664.598..";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 4361);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 467835);
     }
 }
@@ -9,9 +9,22 @@ use nom::{
 };
 
 use crate::parse::{number, parse_lines_to_vec};
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let cards = parse_lines_to_vec::<Card>(input, parse_card)?;
+const NAME: &str = "Scratchcards";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 4, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 4, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 4, 1, EXAMPLE, 13));
+    registry.register_example(Example::new(2023, 4, 2, EXAMPLE, 30));
+}
+
+pub fn parse(input: &str) -> Result<Vec<Card>> {
+    parse_lines_to_vec::<Card>(input, parse_card)
+}
+
+pub fn part1(cards: &[Card]) -> Result<usize> {
     let res = cards
         .iter()
         .map(Card::get_match_count)
@@ -21,8 +34,7 @@ pub fn part1(input: &str) -> Result<usize> {
     Ok(res)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let cards = parse_lines_to_vec::<Card>(input, parse_card)?;
+pub fn part2(cards: &[Card]) -> Result<usize> {
     // how many of each card we have, indexed by ID
     let mut card_instances: HashMap<usize, usize> = cards.iter().map(|c| (c.id, 1)).collect();
 
@@ -42,7 +54,7 @@ pub fn part2(input: &str) -> Result<usize> {
 }
 
 #[derive(Debug)]
-struct Card {
+pub struct Card {
     id: usize,
     winners: HashSet<usize>,
     numbers: HashSet<usize>,
@@ -76,26 +88,26 @@ fn parse_card(input: &str) -> IResult<&str, Card> {
     Ok((input, card))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 13);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 30);
     }
 }
@@ -1,37 +1,45 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
-use nom::bytes::complete::{is_a, is_not, tag};
+use nom::bytes::complete::{is_not, tag};
 use nom::character::complete::{line_ending, space1};
-use nom::combinator::map;
+use nom::error::{Error, ErrorKind};
 use nom::multi::separated_list1;
 use nom::sequence::{terminated, tuple};
 use nom::IResult;
-use rayon::prelude::*;
 use std::sync::Arc;
 
-use crate::parse::{number, parse_all_to};
+use crate::algorithm::interval::{Interval, IntervalSet};
+use crate::parse::{blank_line, labelled, number, parse_all_to, sections, spaced};
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let state = parse_all_to::<State>(input, parse_state)?;
-    Ok(state.part1())
+const NAME: &str = "If You Give A Seed A Fertilizer";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 5, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 5, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 5, 1, EXAMPLE, 35));
+    registry.register_example(Example::new(2023, 5, 2, EXAMPLE, 46));
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let state = parse_all_to::<State>(input, parse_state)?;
-    Ok(state.part2())
+pub fn parse(input: &str) -> Result<State> {
+    parse_all_to::<State>(input, parse_state)
 }
 
-#[derive(Debug)]
-struct State {
-    seeds: Vec<usize>,
-    seed_ranges: Vec<SeedRange>,
-    start_map: Arc<Map>,
+pub fn part1(state: &State) -> Result<usize> {
+    state.part1().ok_or_else(|| anyhow!("there are no seeds"))
+}
+
+pub fn part2(state: &State) -> Result<usize> {
+    state
+        .part2()
+        .ok_or_else(|| anyhow!("there are no seed ranges"))
 }
 
 #[derive(Debug)]
-struct SeedRange {
-    min: usize,
-    max: usize,
+pub struct State {
+    seeds: Vec<usize>,
+    seed_ranges: IntervalSet<usize>,
+    start_map: Arc<Map>,
 }
 
 #[derive(Debug)]
@@ -50,14 +58,11 @@ struct MapRange {
 }
 
 impl State {
-    pub fn new(seeds: Vec<usize>, start_map: Arc<Map>) -> Self {
+    fn new(seeds: Vec<usize>, start_map: Arc<Map>) -> Self {
         let seed_ranges = seeds
             .iter()
             .tuples()
-            .map(|(start, len)| SeedRange {
-                min: *start,
-                max: *start + *len - 1,
-            })
+            .map(|(start, len)| Interval::new(*start, *start + *len))
             .collect();
         Self {
             seeds,
@@ -66,26 +71,12 @@ impl State {
         }
     }
 
-    pub fn part1(&self) -> usize {
-        self.seeds
-            .iter()
-            .map(|s| self.start_map.lookup(*s))
-            .min()
-            .unwrap()
+    pub fn part1(&self) -> Option<usize> {
+        self.seeds.iter().map(|s| self.start_map.lookup(*s)).min()
     }
 
-    pub fn part2(&self) -> usize {
-        self.seed_ranges
-            .par_iter()
-            .map(|range| {
-                (range.min..=range.max)
-                    .into_par_iter()
-                    .map(|s| self.start_map.lookup(s))
-                    .min()
-                    .unwrap()
-            })
-            .min()
-            .unwrap()
+    pub fn part2(&self) -> Option<usize> {
+        self.start_map.lookup_ranges(&self.seed_ranges).min()
     }
 }
 
@@ -101,6 +92,31 @@ impl Map {
             None => m,
         }
     }
+
+    /// Looks up every number in `ns` at once, by splitting them into the
+    /// parts that each range maps
+    pub fn lookup_ranges(&self, ns: &IntervalSet<usize>) -> IntervalSet<usize> {
+        let mut unmapped = ns.clone();
+        let mut mapped = IntervalSet::new();
+        for range in self.ranges.iter() {
+            let src = range.source();
+            // Like `lookup`, numbers are mapped by the first range they're in
+            let parts = unmapped
+                .iter()
+                .filter_map(|i| i.intersection(&src))
+                .collect::<Vec<_>>();
+            for part in parts {
+                mapped.insert(range.lookup_interval(part));
+            }
+            unmapped.remove(src);
+        }
+        // Numbers that aren't in any range map to themselves
+        let ms = mapped.union(&unmapped);
+        match &self.next {
+            Some(next) => next.lookup_ranges(&ms),
+            None => ms,
+        }
+    }
 }
 
 impl MapRange {
@@ -113,29 +129,53 @@ impl MapRange {
     }
 
     pub fn lookup(&self, n: usize) -> Option<usize> {
-        let src_end = self.src_start + self.size - 1;
-        if n < self.src_start || n > src_end {
+        if n < self.src_start || n - self.src_start >= self.size {
             return None;
         }
         let offset = n - self.src_start;
         Some(self.dst_start + offset)
     }
+
+    /// Gets the numbers that the range maps
+    fn source(&self) -> Interval<usize> {
+        Interval::new(self.src_start, self.src_start + self.size)
+    }
+
+    /// Maps `interval`, which has to be within the range's source
+    fn lookup_interval(&self, interval: Interval<usize>) -> Interval<usize> {
+        let start = self.dst_start + (interval.start - self.src_start);
+        Interval::new(start, start + (interval.end - interval.start))
+    }
 }
 
 fn parse_seeds(input: &str) -> IResult<&str, Vec<usize>> {
-    let (input, _) = tag("seeds: ")(input)?;
-    separated_list1(space1, number)(input)
+    let (rest, seeds) = labelled("seeds", spaced(number))(input)?;
+    // The seeds are also read as ranges for part 2, so they have to fit
+    let overflows = seeds
+        .iter()
+        .tuples()
+        .any(|(start, len)| start.checked_add(*len).is_none());
+    if overflows {
+        return Err(too_large(input));
+    }
+    Ok((rest, seeds))
 }
 
 fn parse_map_range(input: &str) -> IResult<&str, MapRange> {
-    map(
-        tuple((
-            terminated(number, tag(" ")),
-            terminated(number, tag(" ")),
-            number,
-        )),
-        |(dst_start, src_start, size)| MapRange::new(src_start, dst_start, size),
-    )(input)
+    let (rest, (dst_start, src_start, size)) = tuple((
+        terminated(number, space1),
+        terminated(number, space1),
+        number,
+    ))(input)?;
+    if src_start.checked_add(size).is_none() || dst_start.checked_add(size).is_none() {
+        return Err(too_large(input));
+    }
+    Ok((rest, MapRange::new(src_start, dst_start, size)))
+}
+
+/// Fails at `input` because a range that starts there goes past `usize::MAX`
+fn too_large(input: &str) -> nom::Err<Error<&str>> {
+    nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))
 }
 
 fn parse_map(input: &str) -> IResult<&str, Map> {
@@ -147,29 +187,25 @@ fn parse_map(input: &str) -> IResult<&str, Map> {
 
 fn parse_state(input: &str) -> IResult<&str, State> {
     let (input, seeds) = parse_seeds(input)?;
-    let (input, _) = is_a("\r\n")(input)?;
-    let (input, maps) = separated_list1(is_a("\r\n"), parse_map)(input)?;
-
-    let mut start_map: Option<Arc<Map>> = None;
-    let mut prev_map: Option<Arc<Map>> = None;
-
-    for mut map in maps.into_iter().rev() {
-        if prev_map.is_some() {
-            map.next = Some(Arc::clone(&prev_map.take().unwrap()));
-        }
-        let map_arc = Arc::new(map);
-        start_map = Some(Arc::clone(&map_arc));
-        prev_map = Some(Arc::clone(&map_arc));
+    let (input, _) = blank_line(input)?;
+    let (input, maps) = sections(parse_map)(input)?;
+
+    // Link the maps together, starting from the last one. There's always at
+    // least one map, so the first map ends up in `start_map`
+    let mut maps = maps.into_iter().rev();
+    let mut start_map = maps
+        .next()
+        .map(Arc::new)
+        .expect("separated_list1 gives at least one map");
+    for mut map in maps {
+        map.next = Some(start_map);
+        start_map = Arc::new(map);
     }
 
-    Ok((input, State::new(seeds, start_map.unwrap())))
+    Ok((input, State::new(seeds, start_map)))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "seeds: 79 14 55 13
+const EXAMPLE: &str = "seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
@@ -203,15 +239,20 @@ humidity-to-location map:
 60 56 37
 56 93 4";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseError;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 35);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 46);
     }
 
@@ -249,4 +290,17 @@ humidity-to-location map:
         assert_eq!(m1.lookup(79), 81);
         assert_eq!(m1.lookup(14), 53);
     }
+
+    #[test]
+    fn test_ranges_that_do_not_fit_are_an_error() {
+        let max = usize::MAX;
+        let input = EXAMPLE.replace("seeds: 79 14", &format!("seeds: 79 {}", max));
+        let err = parse(&input).unwrap_err().downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.expected, "a range that fits in usize");
+
+        let input = EXAMPLE.replace("52 50 48", &format!("{} 50 48", max - 1));
+        let err = parse(&input).unwrap_err().downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (5, 1));
+    }
 }
@@ -1,28 +1,54 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use nom::{
-    bytes::complete::{is_a, tag},
     character::complete::{digit1, line_ending},
     combinator::{map, map_res},
-    multi::separated_list1,
-    sequence::{preceded, separated_pair, terminated},
+    sequence::separated_pair,
     IResult,
 };
 
-use crate::parse::{number, parse_all_to};
+use crate::parse::{labelled, number, parse_all_to, spaced};
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let mut state = parse_all_to(input, parse_state_part1)?;
+const NAME: &str = "Wait For It";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 6, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 6, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 6, 1, EXAMPLE, 288));
+    registry.register_example(Example::new(2023, 6, 2, EXAMPLE, 71503));
+}
+
+pub fn parse(input: &str) -> Result<Sheet> {
+    Ok(Sheet {
+        races: parse_all_to(input, parse_state_part1)?,
+        long_race: parse_all_to(input, parse_state_part2)?,
+    })
+}
+
+pub fn part1(sheet: &Sheet) -> Result<usize> {
+    let mut state = sheet.races.clone();
     let res = state.calculate_win_counts().product();
     Ok(res)
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let mut state = parse_all_to(input, parse_state_part2)?;
-    let res = state.calculate_win_counts().next().unwrap();
+pub fn part2(sheet: &Sheet) -> Result<usize> {
+    let mut state = sheet.long_race.clone();
+    let res = state
+        .calculate_win_counts()
+        .next()
+        .ok_or_else(|| anyhow!("there is no race"))?;
     Ok(res)
 }
 
+/// The sheet of paper, read as a list of races for part 1 and as a single
+/// race (ignoring the spaces between digits) for part 2.
 #[derive(Debug)]
+pub struct Sheet {
+    races: State,
+    long_race: State,
+}
+
+#[derive(Debug, Clone)]
 struct State {
     races: Vec<Race>,
     max_duration: usize,
@@ -30,7 +56,7 @@ struct State {
     time: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Race {
     duration: usize,
     distance: usize,
@@ -39,7 +65,7 @@ struct Race {
 
 impl State {
     pub fn new(races: Vec<Race>) -> Self {
-        let max_duration = races.iter().map(|r| r.duration).max().unwrap();
+        let max_duration = races.iter().map(|r| r.duration).max().unwrap_or(0);
         Self {
             races,
             max_duration,
@@ -87,14 +113,8 @@ impl Race {
 }
 
 fn parse_races_part1(input: &str) -> IResult<&str, Vec<Race>> {
-    let parse_times = preceded(
-        terminated(tag("Time:"), is_a(" ")),
-        separated_list1(is_a(" "), number),
-    );
-    let parse_distances = preceded(
-        terminated(tag("Distance:"), is_a(" ")),
-        separated_list1(is_a(" "), number),
-    );
+    let parse_times = labelled("Time", spaced(number));
+    let parse_distances = labelled("Distance", spaced(number));
     let (input, (times, distances)) =
         separated_pair(parse_times, line_ending, parse_distances)(input)?;
     let races = times
@@ -110,20 +130,12 @@ fn parse_state_part1(input: &str) -> IResult<&str, State> {
 }
 
 fn parse_race_part2(input: &str) -> IResult<&str, Vec<Race>> {
-    let parse_time = map_res(
-        preceded(
-            terminated(tag("Time:"), is_a(" ")),
-            separated_list1(is_a(" "), digit1),
-        ),
-        |parts| parts.join("").parse::<usize>(),
-    );
-    let parse_distance = map_res(
-        preceded(
-            terminated(tag("Distance:"), is_a(" ")),
-            separated_list1(is_a(" "), digit1),
-        ),
-        |parts| parts.join("").parse::<usize>(),
-    );
+    let parse_time = map_res(labelled("Time", spaced(digit1)), |parts| {
+        parts.join("").parse::<usize>()
+    });
+    let parse_distance = map_res(labelled("Distance", spaced(digit1)), |parts| {
+        parts.join("").parse::<usize>()
+    });
     let (input, (duration, distance)) =
         separated_pair(parse_time, line_ending, parse_distance)(input)?;
     let race = Race::new(duration, distance);
@@ -134,22 +146,22 @@ fn parse_state_part2(input: &str) -> IResult<&str, State> {
     map(parse_race_part2, State::new)(input)
 }
 
+const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200";
-
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 288);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 71503);
     }
 }
@@ -7,20 +7,44 @@ use nom::{
 };
 
 use crate::parse::{number, parse_lines_to_vec};
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    run_with_rules(input, ParsingRules::PartOne)
+const NAME: &str = "Camel Cards";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 7, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 7, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 7, 1, EXAMPLE, 6440));
+    registry.register_example(Example::new(2023, 7, 2, EXAMPLE, 5905));
+}
+
+pub fn parse(input: &str) -> Result<Hands> {
+    Ok(Hands {
+        with_jacks: parse_lines_to_vec(input, parse_hand(ParsingRules::PartOne))?,
+        with_jokers: parse_lines_to_vec(input, parse_hand(ParsingRules::PartTwo))?,
+    })
+}
+
+pub fn part1(hands: &Hands) -> Result<usize> {
+    Ok(total_winnings(&hands.with_jacks))
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    run_with_rules(input, ParsingRules::PartTwo)
+pub fn part2(hands: &Hands) -> Result<usize> {
+    Ok(total_winnings(&hands.with_jokers))
 }
 
-fn run_with_rules(input: &str, rules: ParsingRules) -> Result<usize> {
-    let mut hands = parse_lines_to_vec(input, parse_hand(rules))?;
+fn total_winnings(hands: &[Hand]) -> usize {
+    let mut hands = hands.iter().collect::<Vec<_>>();
     hands.sort();
-    let res = hands.iter().enumerate().map(|(i, h)| (i + 1) * h.bid).sum();
-    Ok(res)
+    hands.iter().enumerate().map(|(i, h)| (i + 1) * h.bid).sum()
+}
+
+/// The list of hands, read with 'J' as a Jack for part 1 and as a Joker for
+/// part 2.
+#[derive(Debug)]
+pub struct Hands {
+    with_jacks: Vec<Hand>,
+    with_jokers: Vec<Hand>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -107,10 +131,10 @@ impl TryFrom<&[Card]> for HandType {
         card_count_map.remove(&Card::Joker);
         // count of card types, excluding Jokers
         let card_counts = card_count_map.into_values().collect::<Vec<_>>();
-        let has_five_same = card_counts.iter().any(|c| *c == 5);
-        let has_four_same = card_counts.iter().any(|c| *c == 4);
-        let has_three_same = card_counts.iter().any(|c| *c == 3);
-        let has_two_same = card_counts.iter().any(|c| *c == 2);
+        let has_five_same = card_counts.contains(&5);
+        let has_four_same = card_counts.contains(&4);
+        let has_three_same = card_counts.contains(&3);
+        let has_two_same = card_counts.contains(&2);
 
         if has_five_same
             || joker_count == 5
@@ -210,25 +234,25 @@ fn parse_hand(rules: ParsingRules) -> impl FnMut(&str) -> IResult<&str, Hand> {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "32T3K 765
+const EXAMPLE: &str = "32T3K 765
 T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 6440);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 5905);
     }
 
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, char, line_ending, one_of},
@@ -10,23 +10,36 @@ use nom::{
     IResult,
 };
 
+use crate::algorithm::number_theory;
 use crate::parse::parse_all_to;
+use crate::solver::{Example, FnSolver, Registry};
 
-pub fn part1(input: &str) -> Result<usize> {
-    let state = parse_all_to(input, parse_state)?;
-    Ok(state.steps_to_find("ZZZ"))
+const NAME: &str = "Haunted Wasteland";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 8, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 8, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 8, 1, EXAMPLE1, 6));
+    registry.register_example(Example::new(2023, 8, 2, EXAMPLE2, 6));
+}
+
+pub fn parse(input: &str) -> Result<State> {
+    parse_all_to(input, parse_state)
+}
+
+pub fn part1(state: &State) -> Result<usize> {
+    state.steps_to_find("ZZZ")
 }
 
-pub fn part2(input: &str) -> Result<usize> {
-    let state = parse_all_to(input, parse_state)?;
-    Ok(state.steps_for_part2())
+pub fn part2(state: &State) -> Result<usize> {
+    state.steps_for_part2()
 }
 
 #[derive(Debug)]
-struct State<'a> {
+pub struct State {
     directions: Vec<Direction>,
     // map of node ID to (Left, Right) next node
-    nodes: HashMap<&'a str, (&'a str, &'a str)>,
+    nodes: HashMap<String, (String, String)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,80 +48,56 @@ enum Direction {
     Right,
 }
 
-impl<'a> State<'a> {
-    pub fn steps_to_find(&self, target: &str) -> usize {
+impl State {
+    pub fn steps_to_find(&self, target: &str) -> Result<usize> {
+        self.steps_from("AAA", |key| key == target)
+    }
+
+    /// Counts the steps it takes to follow the directions from `start` to the
+    /// first node that `is_end` accepts
+    fn steps_from(&self, start: &str, is_end: impl Fn(&str) -> bool) -> Result<usize> {
         let mut directions_cyle = self.directions.iter().cycle();
         let mut steps = 0;
-        let mut key = "AAA";
-
-        loop {
-            if key == target {
-                break;
-            }
-            let dir = directions_cyle.next().unwrap();
-            let next = self.nodes.get(key).unwrap();
+        let mut key = start;
+
+        while !is_end(key) {
+            let dir = directions_cyle
+                .next()
+                .ok_or_else(|| anyhow!("there are no directions"))?;
+            let next = self
+                .nodes
+                .get(key)
+                .ok_or_else(|| anyhow!("there is no node named {}", key))?;
             key = match dir {
-                Direction::Left => next.0,
-                Direction::Right => next.1,
+                Direction::Left => next.0.as_str(),
+                Direction::Right => next.1.as_str(),
             };
             steps += 1;
         }
-        steps
+        Ok(steps)
     }
 
-    pub fn steps_for_part2(&self) -> usize {
+    pub fn steps_for_part2(&self) -> Result<usize> {
         // Starting points for our "ghosts"
         let ghost_keys = self
             .nodes
             .keys()
             .filter(|k| k.ends_with('A'))
-            .copied()
+            .map(String::as_str)
             .collect::<Vec<&str>>();
 
         // Entries are how long each "ghost" took to find their first end point
-        let end_steps = ghost_keys.into_iter().map(|k| {
-            let mut directions_cyle = self.directions.iter().cycle();
-            let mut steps = 0;
-            let mut key = k;
-            loop {
-                if key.ends_with('Z') {
-                    break;
-                }
-                let dir = directions_cyle.next().unwrap();
-                let next = self.nodes.get(key).unwrap();
-                key = match dir {
-                    Direction::Left => next.0,
-                    Direction::Right => next.1,
-                };
-                steps += 1;
-            }
-            steps
-        });
+        let end_steps = ghost_keys
+            .into_iter()
+            .map(|k| self.steps_from(k, |key| key.ends_with('Z')))
+            .collect::<Result<Vec<_>>>()?;
 
         // Find the lowest common multiple of all of the ghosts' end points
-        end_steps.reduce(lcm).unwrap()
-    }
-}
-
-fn lcm(first: usize, second: usize) -> usize {
-    first * second / gcd(first, second)
-}
-
-fn gcd(first: usize, second: usize) -> usize {
-    let mut max = first;
-    let mut min = second;
-    if min > max {
-        std::mem::swap(&mut max, &mut min);
-    }
-
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+        if end_steps.is_empty() {
+            bail!("there are no nodes ending in 'A'");
         }
-
-        max = min;
-        min = res;
+        number_theory::checked_lcm_all(end_steps)
+            .ok_or_else(|| anyhow!("the number of steps is too big to count"))
     }
 }
 
@@ -124,7 +113,7 @@ impl TryFrom<char> for Direction {
     }
 }
 
-fn parse_state(input: &str) -> IResult<&str, State<'_>> {
+fn parse_state(input: &str) -> IResult<&str, State> {
     // Directions is a line of "LRLRLRLRLRLR"
     let parse_directions = terminated(
         many1(map_res(one_of("LR"), Direction::try_from)),
@@ -150,28 +139,21 @@ fn parse_state(input: &str) -> IResult<&str, State<'_>> {
         input,
         State {
             directions,
-            nodes: nodes.into_iter().collect(),
+            nodes: nodes
+                .into_iter()
+                .map(|(id, (l, r))| (id.to_string(), (l.to_string(), r.to_string())))
+                .collect(),
         },
     ))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "LLR
+const EXAMPLE1: &str = "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
-    #[test]
-    fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
-        assert_eq!(res, 6);
-    }
-
-    const INPUT2: &str = "LR
+const EXAMPLE2: &str = "LR
 
 OOA = (OOB, XXX)
 OOB = (XXX, OOZ)
@@ -182,9 +164,26 @@ TTC = (TTZ, TTZ)
 TTZ = (TTB, TTB)
 XXX = (XXX, XXX)";
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_part1_gives_correct_answer() {
+        let res = part1(&parse(EXAMPLE1).unwrap()).unwrap();
+        assert_eq!(res, 6);
+    }
+
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT2).unwrap();
+        let res = part2(&parse(EXAMPLE2).unwrap()).unwrap();
         assert_eq!(res, 6);
     }
+
+    #[test]
+    fn test_a_missing_node_is_an_error() {
+        let state = parse("L\n\nAAA = (BBB, BBB)").unwrap();
+        let err = part1(&state).unwrap_err();
+        assert_eq!(err.to_string(), "there is no node named BBB");
+    }
 }
@@ -1,21 +1,29 @@
 use anyhow::Result;
 use itertools::Itertools;
-use nom::{
-    character::complete::{char, digit1},
-    combinator::{map_res, opt, recognize},
-    multi::{many1, separated_list1},
-    sequence::preceded,
-    IResult,
-};
-
-use crate::parse::parse_lines_to_vec;
-
-pub fn part1(input: &str) -> Result<i64> {
-    solve(input, ExtrapolateDir::Forwards)
+use nom::IResult;
+
+use crate::parse::{parse_lines_to_vec, signed, spaced};
+use crate::solver::{Example, FnSolver, Registry};
+
+const NAME: &str = "Mirage Maintenance";
+
+pub fn register(registry: &mut Registry) {
+    registry.register(FnSolver::new(2023, 9, 1, NAME, parse, part1));
+    registry.register(FnSolver::new(2023, 9, 2, NAME, parse, part2));
+    registry.register_example(Example::new(2023, 9, 1, EXAMPLE, 114));
+    registry.register_example(Example::new(2023, 9, 2, EXAMPLE, 2));
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>> {
+    parse_lines_to_vec(input, parse_line)
+}
+
+pub fn part1(rows: &[Vec<i64>]) -> Result<i64> {
+    Ok(solve(rows, ExtrapolateDir::Forwards))
 }
 
-pub fn part2(input: &str) -> Result<i64> {
-    solve(input, ExtrapolateDir::Backwards)
+pub fn part2(rows: &[Vec<i64>]) -> Result<i64> {
+    Ok(solve(rows, ExtrapolateDir::Backwards))
 }
 
 #[derive(Clone, Copy)]
@@ -24,14 +32,12 @@ enum ExtrapolateDir {
     Backwards,
 }
 
-fn solve(input: &str, dir: ExtrapolateDir) -> Result<i64> {
-    let rows = parse_lines_to_vec(input, parse_line)?;
-    let res = rows.into_iter().map(|r| process_row(r, dir)).sum();
-    Ok(res)
+fn solve(rows: &[Vec<i64>], dir: ExtrapolateDir) -> i64 {
+    rows.iter().map(|r| process_row(r, dir)).sum()
 }
 
-fn process_row(row: Vec<i64>, dir: ExtrapolateDir) -> i64 {
-    let mut diff_rows = vec![row];
+fn process_row(row: &[i64], dir: ExtrapolateDir) -> i64 {
+    let mut diff_rows = vec![row.to_vec()];
     let mut found_end = false;
 
     while !found_end {
@@ -44,14 +50,14 @@ fn process_row(row: Vec<i64>, dir: ExtrapolateDir) -> i64 {
         .iter()
         .rev()
         .map(|v| match dir {
-            ExtrapolateDir::Forwards => *v.last().unwrap(),
-            ExtrapolateDir::Backwards => *v.first().unwrap(),
+            // A row with no differences left is extrapolated as all zeros
+            ExtrapolateDir::Forwards => v.last().copied().unwrap_or(0),
+            ExtrapolateDir::Backwards => v.first().copied().unwrap_or(0),
         })
-        .reduce(|acc, l| match dir {
+        .fold(0, |acc, l| match dir {
             ExtrapolateDir::Forwards => l + acc,
             ExtrapolateDir::Backwards => l - acc,
         })
-        .unwrap()
 }
 
 fn get_diffs(row: &[i64]) -> Vec<i64> {
@@ -63,30 +69,26 @@ fn is_all_zeros(row: &[i64]) -> bool {
 }
 
 fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
-    let number = map_res(
-        recognize(preceded(opt(char('-')), many1(digit1))),
-        |n: &str| n.parse::<i64>(),
-    );
-    separated_list1(char(' '), number)(input)
+    spaced(signed)(input)
 }
 
+const EXAMPLE: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const INPUT: &str = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-
     #[test]
     fn test_part1_gives_correct_answer() {
-        let res = part1(INPUT).unwrap();
+        let res = part1(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 114);
     }
 
     #[test]
     fn test_part2_gives_correct_answer() {
-        let res = part2(INPUT).unwrap();
+        let res = part2(&parse(EXAMPLE).unwrap()).unwrap();
         assert_eq!(res, 2);
     }
 }
@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+/// One of the four directions that move along the rows and columns of a
+/// grid. Deltas follow the grid's coordinates, so y increases to the south.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// One of the eight directions that move to a neighbouring cell of a grid,
+/// including diagonally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Clockwise from north
+    pub const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Turns a quarter turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+
+    /// Turns a quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// Gets the `(dx, dy)` for one step in this direction
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::North | Self::South)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Self::North => '↑',
+            Self::East => '→',
+            Self::South => '↓',
+            Self::West => '←',
+        }
+    }
+}
+
+impl Direction8 {
+    /// Clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Turns an eighth of a turn anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turns an eighth of a turn clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /// Gets the `(dx, dy)` for one step in this direction
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (1, 1),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Self::North => '↑',
+            Self::NorthEast => '↗',
+            Self::East => '→',
+            Self::SouthEast => '↘',
+            Self::South => '↓',
+            Self::SouthWest => '↙',
+            Self::West => '←',
+            Self::NorthWest => '↖',
+        }
+    }
+
+    /// Where the direction is in `ALL`
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::North => Self::North,
+            Direction::East => Self::East,
+            Direction::South => Self::South,
+            Direction::West => Self::West,
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    /// Reads `U`/`D`/`L`/`R`, `N`/`S`/`E`/`W`, `^`/`v`/`<`/`>` or an arrow
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | 'N' | '^' | '↑' => Ok(Self::North),
+            'R' | 'E' | '>' | '→' => Ok(Self::East),
+            'D' | 'S' | 'v' | '↓' => Ok(Self::South),
+            'L' | 'W' | '<' | '←' => Ok(Self::West),
+            value => Err(format!("invalid direction: {}", value)),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction8 {
+    type Error = String;
+
+    /// Reads anything a `Direction` can be read from, or a diagonal arrow
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '↗' => Ok(Self::NorthEast),
+            '↘' => Ok(Self::SouthEast),
+            '↙' => Ok(Self::SouthWest),
+            '↖' => Ok(Self::NorthWest),
+            value => Direction::try_from(value).map(Self::from),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl Display for Direction8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_it_turns() {
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+        }
+
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+        assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+    }
+
+    #[test]
+    fn test_reversing_negates_the_delta() {
+        for dir in Direction8::ALL {
+            let (dx, dy) = dir.delta();
+            assert_eq!(dir.reverse().delta(), (-dx, -dy));
+            assert_eq!(dir.is_diagonal(), dx != 0 && dy != 0);
+        }
+        for dir in Direction::ALL {
+            assert_eq!(Direction8::from(dir).delta(), dir.delta());
+        }
+    }
+
+    #[test]
+    fn test_it_parses_directions() {
+        for (chars, dir) in [("UN^↑", Direction::North), ("LW<←", Direction::West)] {
+            for c in chars.chars() {
+                assert_eq!(Direction::try_from(c), Ok(dir));
+            }
+        }
+        assert_eq!(Direction8::try_from('↘'), Ok(Direction8::SouthEast));
+        assert_eq!(Direction8::try_from('v'), Ok(Direction8::South));
+        assert!(Direction::try_from('x').is_err());
+    }
+}
@@ -0,0 +1,315 @@
+use anyhow::{bail, Result};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::direction::{Direction, Direction8};
+use crate::parse::{char_grid, parse_all_to, ParseError};
+
+/// A rectangular grid of cells. Cells are found by `(x, y)` coordinates,
+/// where `(0, 0)` is the top left cell and y increases downwards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    w: usize,
+    h: usize,
+    /// Row by row from the top
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let w = rows.first().map_or(0, Vec::len);
+        let h = rows.len();
+        let mut cells = Vec::with_capacity(w * h);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != w {
+                bail!(
+                    "row {} has {} cells, but the first row has {}",
+                    y,
+                    row.len(),
+                    w
+                );
+            }
+            cells.extend(row);
+        }
+        Ok(Self { w, h, cells })
+    }
+
+    /// Parses a grid with a cell for each character in `input`, using
+    /// `char_grid`. `parse_cell` gives `None` for characters that can't be in
+    /// the grid, which are reported as errors along with `expected`. Empty
+    /// input gives an empty grid.
+    pub fn parse<F>(input: &str, mut parse_cell: F, expected: &str) -> Result<Self>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        if input.is_empty() {
+            return Self::from_rows(vec![]);
+        }
+        let mut rejected = false;
+        let cell = |c| {
+            let cell = parse_cell(c);
+            rejected = cell.is_none();
+            cell
+        };
+        parse_all_to(input, char_grid(cell)).map_err(|e| match e.downcast::<ParseError>() {
+            Ok(e) if rejected => ParseError {
+                expected: expected.to_string(),
+                ..e
+            }
+            .into(),
+            Ok(e) => e.into(),
+            Err(e) => e,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    /// Is `(x, y)` a cell in the grid?
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.w && y < self.h
+    }
+
+    pub fn get(&self, coord: (usize, usize)) -> Option<&T> {
+        self.contains(coord)
+            .then(|| &self.cells[coord.1 * self.w + coord.0])
+    }
+
+    pub fn get_mut(&mut self, coord: (usize, usize)) -> Option<&mut T> {
+        self.contains(coord)
+            .then(|| &mut self.cells[coord.1 * self.w + coord.0])
+    }
+
+    /// Gets the coordinate `(dx, dy)` away from `(x, y)`, if it's in the grid
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let coord = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(coord).then_some(coord)
+    }
+
+    /// Gets the coordinate one step from `coord` in `direction`, if it's in
+    /// the grid
+    pub fn step(&self, coord: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(coord, direction.delta())
+    }
+
+    /// Gets the coordinates of the cells that share an edge with `coord`,
+    /// clockwise from the one above it
+    pub fn neighbours4(&self, coord: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(coord, dir))
+    }
+
+    /// Gets the coordinates of the cells that share an edge or a corner with
+    /// `coord`, clockwise from the one above it
+    pub fn neighbours8(&self, coord: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(coord, dir.delta()))
+    }
+
+    /// Gets every coordinate in the grid, row by row from the top left
+    pub fn coords(&self) -> impl Iterator<Item = (usize, usize)> {
+        let w = self.w;
+        (0..self.h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    /// Gets every cell along with its coordinate, row by row from the top left
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    /// Finds the coordinate of the first cell, row by row from the top left,
+    /// that matches `predicate`
+    pub fn position<P>(&self, mut predicate: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(c, _)| c)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.w..(y + 1) * self.w]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.h).map(|y| self.row(y))
+    }
+
+    /// Gets the cells in column `x`, from the top
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.w,
+            "column {} is outside the {}-wide grid",
+            x,
+            self.w
+        );
+        self.cells.iter().skip(x).step_by(self.w)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.w).map(|x| self.column(x))
+    }
+
+    /// Creates a grid of the same size by applying `f` to every cell
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            w: self.w,
+            h: self.h,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps the rows and columns, so that `(x, y)` moves to `(y, x)`
+    pub fn transpose(&self) -> Self {
+        Self {
+            w: self.h,
+            h: self.w,
+            cells: self.columns().flat_map(|col| col.cloned()).collect(),
+        }
+    }
+
+    /// Turns the grid a quarter turn clockwise, so that the left column
+    /// becomes the top row
+    pub fn rotate_clockwise(&self) -> Self {
+        Self {
+            w: self.h,
+            h: self.w,
+            cells: (0..self.w)
+                .flat_map(|x| (0..self.h).rev().map(move |y| self[(x, y)].clone()))
+                .collect(),
+        }
+    }
+
+    /// Turns the grid a quarter turn anticlockwise, so that the right column
+    /// becomes the top row
+    pub fn rotate_anticlockwise(&self) -> Self {
+        Self {
+            w: self.h,
+            h: self.w,
+            cells: (0..self.w)
+                .rev()
+                .flat_map(|x| (0..self.h).map(move |y| self[(x, y)].clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: (usize, usize)) -> &Self::Output {
+        let (w, h) = (self.w, self.h);
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", coord, w, h))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, coord: (usize, usize)) -> &mut Self::Output {
+        let (w, h) = (self.w, self.h);
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", coord, w, h))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| c.to_digit(10), "a digit").unwrap()
+    }
+
+    #[test]
+    fn test_it_parses_a_grid() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 1)], 4);
+        assert_eq!(grid.get((2, 0)), Some(&3));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(digits("").width(), 0);
+    }
+
+    #[test]
+    fn test_it_reports_where_a_grid_is_invalid() {
+        let err = Grid::parse("12\n3x", |c| c.to_digit(10), "a digit").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a digit");
+
+        let err = Grid::parse("12\n3", |c| c.to_digit(10), "a digit").unwrap_err();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "every line to be as long as the first");
+
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn test_it_finds_neighbours_in_the_grid() {
+        let grid = digits("123\n456\n789");
+        let mut edges = grid.neighbours4((0, 0)).collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(edges, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_it_iterates_over_rows_and_columns() {
+        let grid = digits("12\n34\n56");
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![[1, 2], [3, 4], [5, 6]]
+        );
+        let columns = grid
+            .columns()
+            .map(|col| col.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec![vec![1, 3, 5], vec![2, 4, 6]]);
+        assert_eq!(grid.position(|n| n % 2 == 0), Some((1, 0)));
+    }
+
+    #[test]
+    fn test_it_transposes_and_rotates() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "36\n25\n14\n");
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+    }
+}
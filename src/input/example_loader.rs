@@ -0,0 +1,40 @@
+use anyhow::{anyhow, Result};
+
+use crate::solver::registry;
+
+/// Loads the example input from the puzzle description, as registered by each
+/// day. Examples often differ between parts, so the loader is for one part.
+pub struct ExampleLoader {
+    part: u16,
+}
+
+impl ExampleLoader {
+    pub fn new(part: u16) -> Self {
+        Self { part }
+    }
+}
+
+impl super::Loader for ExampleLoader {
+    fn load(&self, year: u16, day: u16) -> Result<String> {
+        registry()
+            .example(year, day, self.part)
+            .map(|e| e.input.to_string())
+            .ok_or_else(|| anyhow!("{} day {} part {} has no example", year, day, self.part))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::Loader;
+
+    #[test]
+    fn test_it_loads_the_example_for_the_part() {
+        let part1 = ExampleLoader::new(1).load(2023, 1).unwrap();
+        let part2 = ExampleLoader::new(2).load(2023, 1).unwrap();
+        assert!(part1.starts_with("1abc2"));
+        assert!(part2.starts_with("two1nine"));
+        assert!(ExampleLoader::new(1).load(2023, 25).is_err());
+        assert!(ExampleLoader::new(1).load(2022, 1).is_err());
+    }
+}
@@ -15,9 +15,10 @@ impl FileLoader {
 }
 
 impl super::Loader for FileLoader {
-    fn load(&self, day: u16) -> Result<String> {
+    /// Loads the file `{year}/day{day}.txt` under the loader's path
+    fn load(&self, year: u16, day: u16) -> Result<String> {
         let file_name = format!("day{}.txt", day);
-        let full_path = self.path.join(file_name);
+        let full_path = self.path.join(year.to_string()).join(file_name);
         std::fs::read_to_string(full_path).context("could not read input file")
     }
 }
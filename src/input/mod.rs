@@ -1,7 +1,12 @@
 use anyhow::Result;
 
+pub mod example_loader;
 pub mod file_loader;
+pub mod normalise;
+pub mod path_loader;
+pub mod stdin_loader;
+pub mod string_loader;
 
 pub trait Loader {
-    fn load(&self, day: u16) -> Result<String>;
+    fn load(&self, year: u16, day: u16) -> Result<String>;
 }
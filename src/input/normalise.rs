@@ -0,0 +1,39 @@
+/// Tidies up puzzle input so that parsers only ever see one form of it,
+/// however it was saved:
+///
+/// - a byte order mark at the start is removed
+/// - `\r\n` and lone `\r` line endings become `\n`
+/// - whitespace at the end of each line, and blank lines at the end of the
+///   input, are removed
+///
+/// The input never ends with a line ending, just like the examples.
+pub fn normalise(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut res = input
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .split('\n')
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    res.truncate(res.trim_end().len());
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_it_unifies_line_endings() {
+        assert_eq!(normalise("a\r\nb\rc\nd"), "a\nb\nc\nd");
+        assert_eq!(normalise("\u{feff}a\r\nb\r\n"), "a\nb");
+    }
+
+    #[test]
+    fn test_it_trims_trailing_whitespace() {
+        assert_eq!(normalise("a  \n\tb\t\n\n \n"), "a\n\tb");
+        assert_eq!(normalise("a\n\nb"), "a\n\nb");
+        assert_eq!(normalise(" \r\n"), "");
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Loads the input from one file, whichever puzzle it's for.
+pub struct PathLoader {
+    path: PathBuf,
+}
+
+impl PathLoader {
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self { path: path.into() }
+    }
+}
+
+impl super::Loader for PathLoader {
+    fn load(&self, _year: u16, _day: u16) -> Result<String> {
+        std::fs::read_to_string(&self.path)
+            .with_context(|| format!("could not read input file {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::Loader;
+
+    #[test]
+    fn test_it_loads_the_same_file_for_every_day() {
+        let path = std::env::temp_dir().join(format!("path_loader_{}.txt", std::process::id()));
+        std::fs::write(&path, "1abc2").unwrap();
+        let loader = PathLoader::new(&path);
+
+        assert_eq!(loader.load(2023, 1).unwrap(), "1abc2");
+        assert_eq!(loader.load(2022, 7).unwrap(), "1abc2");
+        std::fs::remove_file(&path).unwrap();
+        assert!(loader.load(2023, 1).is_err());
+    }
+}
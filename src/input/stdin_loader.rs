@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+use std::{cell::OnceCell, io::Read};
+
+/// Loads the input from stdin, whichever puzzle it's for. Stdin is only read
+/// the first time; later loads get the same input.
+#[derive(Default)]
+pub struct StdinLoader {
+    input: OnceCell<String>,
+}
+
+impl StdinLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl super::Loader for StdinLoader {
+    fn load(&self, _year: u16, _day: u16) -> Result<String> {
+        if let Some(input) = self.input.get() {
+            return Ok(input.clone());
+        }
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("could not read input from stdin")?;
+        Ok(self.input.get_or_init(|| input).clone())
+    }
+}
@@ -0,0 +1,38 @@
+//! Solutions to Advent of Code puzzles.
+//!
+//! Each `dayN` module has a `parse` function that turns the puzzle input into
+//! the type its `part1` and `part2` functions solve, and a `register`
+//! function that adds them to a [`solver::Registry`]. The shared
+//! [`solver::registry`] has every implemented puzzle, which can be run by
+//! building a [`puzzle::Puzzle`] and giving it an [`input::Loader`].
+
+pub mod algorithm;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod direction;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod point;
+pub mod puzzle;
+pub mod report;
+pub mod solver;
@@ -1,52 +1,224 @@
-use anyhow::Result;
-use clap::Parser;
-
-use crate::input::file_loader::FileLoader;
-
-mod algorithm;
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod input;
-mod parse;
-mod point;
-mod puzzle;
+use anyhow::{bail, Result};
+use clap::builder::RangedU64ValueParser;
+use clap::{ArgGroup, Parser, Subcommand};
+use std::path::PathBuf;
+
+use advent_of_code_2023::answers::Answers;
+use advent_of_code_2023::input::{
+    example_loader::ExampleLoader, file_loader::FileLoader, path_loader::PathLoader,
+    stdin_loader::StdinLoader, Loader,
+};
+use advent_of_code_2023::{bench, puzzle, report, solver};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    /// e.g. '1-2' to run the solution for day 1, part 2
-    #[arg(value_parser = str_to_puzzle)]
-    puzzle: puzzle::Puzzle,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// e.g. '1-2' to run the solution for day 1, part 2, or '2023-1-2' to
+    /// pick the year as well
+    #[arg(value_parser = str_to_puzzle, required_unless_present = "list")]
+    puzzle: Option<puzzle::Puzzle>,
+
+    /// List the implemented puzzles instead of running one
+    #[arg(long, conflicts_with = "puzzle")]
+    list: bool,
+
+    /// Run the puzzle on the example from its description and check the answer
+    #[arg(long, requires = "puzzle", conflicts_with_all = ["input", "check"])]
+    example: bool,
+
+    /// Compare each answer with the one stored in input/answers.toml
+    #[arg(long, global = true)]
+    check: bool,
+
+    /// Read the input from this file instead of input/YEAR/dayN.txt, or from stdin
+    /// if it's '-'
+    #[arg(long, global = true)]
+    input: Option<PathBuf>,
+
+    /// How to write the results of running puzzles
+    #[arg(long, value_enum, global = true, default_value_t)]
+    format: report::Format,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run every implemented puzzle for a range of days and summarise the results
+    Run {
+        /// e.g. 'all', '5', '1-18' or '5-*', optionally after a year as in
+        /// '2023-5-*'
+        #[arg(value_parser = str_to_day_range)]
+        days: puzzle::DayRange,
+    },
+    /// Time a puzzle, or a range of days, over repeated runs
+    #[command(group(ArgGroup::new("target").required(true)))]
+    Bench {
+        /// e.g. '1-2' to benchmark the solution for day 1, part 2
+        #[arg(value_parser = str_to_puzzle, group = "target")]
+        puzzle: Option<puzzle::Puzzle>,
+
+        /// Benchmark every puzzle for a range of days, e.g. 'all' or '5-*'
+        #[arg(long, value_parser = str_to_day_range, group = "target")]
+        days: Option<puzzle::DayRange>,
+
+        /// Number of timed runs
+        #[arg(long, default_value_t = 10, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        runs: usize,
+
+        /// Number of untimed runs before the timed ones
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+    },
 }
 
 fn str_to_puzzle(s: &str) -> Result<puzzle::Puzzle, String> {
     puzzle::Puzzle::try_from(s)
 }
 
+fn str_to_day_range(s: &str) -> Result<puzzle::DayRange, String> {
+    puzzle::DayRange::try_from(s)
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    println!("Running {}", cli.puzzle);
+    let loader: Box<dyn Loader> = match (cli.input, &cli.puzzle) {
+        (_, Some(puzzle)) if cli.example => Box::new(ExampleLoader::new(puzzle.part())),
+        (Some(path), _) if path.as_os_str() == "-" => Box::new(StdinLoader::new()),
+        (Some(path), _) => Box::new(PathLoader::new(path)),
+        (None, _) => Box::new(FileLoader::new("./input")),
+    };
+    let answers = if cli.example {
+        Some(Answers::examples())
+    } else if cli.check {
+        Some(Answers::load("./input/answers.toml")?)
+    } else {
+        None
+    };
+
+    match cli.command {
+        Some(Command::Run { days }) => {
+            return run_all(days, loader.as_ref(), answers.as_ref(), cli.format)
+        }
+        Some(Command::Bench {
+            puzzle,
+            days,
+            runs,
+            warmup,
+        }) => {
+            let puzzles = match (puzzle, days) {
+                (Some(puzzle), _) => vec![puzzle],
+                (None, Some(days)) => {
+                    let puzzles = days.puzzles();
+                    if puzzles.is_empty() {
+                        bail!("no puzzles are implemented for {}", days);
+                    }
+                    puzzles
+                }
+                (None, None) => unreachable!("clap requires a puzzle or days"),
+            };
+            let mut failures = 0;
+            for puzzle in puzzles.iter() {
+                match bench::bench(puzzle, loader.as_ref(), warmup, runs) {
+                    Ok(result) => println!("{}", result),
+                    Err(e) => {
+                        println!("{}: error: {}", puzzle, e);
+                        failures += 1;
+                    }
+                }
+            }
+            if failures > 0 {
+                bail!("{} of {} puzzles failed", failures, puzzles.len());
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    let Some(puzzle) = cli.puzzle else {
+        for solver in solver::registry().iter() {
+            println!(
+                "{}-{}-{}\t{}",
+                solver.year(),
+                solver.day(),
+                solver.part(),
+                solver.name()
+            );
+        }
+        return Ok(());
+    };
+
+    if cli.format != report::Format::Text {
+        let mut outcome = report::Outcome::new(puzzle.clone(), puzzle.run(loader.as_ref()));
+        if let Some(answers) = &answers {
+            outcome.check(answers);
+        }
+        report::write_outcomes(
+            &mut std::io::stdout().lock(),
+            std::slice::from_ref(&outcome),
+            cli.format,
+        )?;
+        if outcome.is_failure() {
+            bail!("{} failed", puzzle);
+        }
+        return Ok(());
+    }
+
+    if cli.example {
+        println!("Running {} on the example", puzzle);
+    } else {
+        println!("Running {}", puzzle);
+    }
+
+    let (result, timings) = puzzle.run(loader.as_ref())?;
+    println!(
+        "{} (parse {:?}, solve {:?})",
+        result,
+        timings.parse.unwrap_or_default(),
+        timings.solve
+    );
+
+    if let Some(answers) = answers {
+        let verdict = answers.check(puzzle.year(), puzzle.day(), puzzle.part(), &result);
+        println!("{}", verdict);
+        if verdict.is_fail() {
+            bail!("{} gave the wrong answer", puzzle);
+        }
+    }
+
+    Ok(())
+}
 
-    let file_loader = FileLoader::new("./input");
+fn run_all(
+    days: puzzle::DayRange,
+    loader: &dyn Loader,
+    answers: Option<&Answers>,
+    format: report::Format,
+) -> Result<()> {
+    let puzzles = days.puzzles();
+    if puzzles.is_empty() {
+        bail!("no puzzles are implemented for {}", days);
+    }
+    let results = puzzle::run_all(&puzzles, loader);
+    let outcomes = puzzles
+        .into_iter()
+        .zip(results)
+        .map(|(puzzle, result)| {
+            let mut outcome = report::Outcome::new(puzzle, result);
+            if let Some(answers) = answers {
+                outcome.check(answers);
+            }
+            outcome
+        })
+        .collect::<Vec<_>>();
 
-    let (result, duration) = cli.puzzle.run(file_loader)?;
-    println!("{} ({:?})", result, duration);
+    report::write_outcomes(&mut std::io::stdout().lock(), &outcomes, format)?;
 
+    let failures = outcomes.iter().filter(|o| o.is_failure()).count();
+    if failures > 0 {
+        bail!("{} of {} puzzles failed", failures, outcomes.len());
+    }
     Ok(())
 }
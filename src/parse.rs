@@ -1,35 +1,366 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use nom::{
-    character::complete::{digit1, line_ending},
-    combinator::{all_consuming, map_res, opt},
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::ErrorKind,
     multi::separated_list1,
-    sequence::terminated,
+    sequence::{pair, preceded, terminated, tuple},
     IResult, Parser,
 };
+use std::{fmt::Display, str::FromStr};
+
+use crate::grid::Grid;
+
+/// How many characters either side of an error are shown in its snippet
+const SNIPPET_RADIUS: usize = 20;
+
+/// Where parsing an input failed, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counting from 1
+    pub line: usize,
+    /// Counting from 1, in characters
+    pub column: usize,
+    /// Text from around the error on its line
+    pub snippet: String,
+    /// What the parser wanted to find, e.g. "a digit"
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error at character `char_index` of line `line_index` of
+    /// `input`, where both count from 0.
+    pub fn at<S>(input: &str, line_index: usize, char_index: usize, expected: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let line = input.lines().nth(line_index).unwrap_or_default();
+        let snippet = line
+            .chars()
+            .skip(char_index.saturating_sub(SNIPPET_RADIUS))
+            .take(char_index.min(SNIPPET_RADIUS) + SNIPPET_RADIUS + 1)
+            .collect();
+        Self {
+            line: line_index + 1,
+            column: char_index + 1,
+            snippet,
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error at byte `offset` of `input`
+    pub fn at_offset<S>(input: &str, offset: usize, expected: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_index = before.matches('\n').count();
+        let char_index = before[line_start..].chars().count();
+        Self::at(input, line_index, char_index, expected)
+    }
+
+    /// Converts an error from a nom parser that was given `input`
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                // nom's errors hold whatever was left of the input when the
+                // parser failed
+                let offset = input.len().saturating_sub(e.input.len());
+                Self::at_offset(input, offset, describe(e.code))
+            }
+            nom::Err::Incomplete(_) => Self::at_offset(input, input.len(), "more input"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {} near \"{}\"",
+            self.line, self.column, self.expected, self.snippet
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Describes what a nom parser that failed with `kind` was looking for
+fn describe(kind: ErrorKind) -> String {
+    let description = match kind {
+        ErrorKind::Tag => "a keyword or separator",
+        ErrorKind::Char => "a particular character",
+        ErrorKind::OneOf | ErrorKind::IsA => "one of a set of characters",
+        ErrorKind::NoneOf | ErrorKind::IsNot => "a different character",
+        ErrorKind::Digit => "a digit",
+        ErrorKind::HexDigit => "a hex digit",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value",
+        ErrorKind::Many1 | ErrorKind::Many1Count | ErrorKind::SeparatedList => "at least one item",
+        ErrorKind::Alt => "one of several alternatives",
+        ErrorKind::LengthValue => "every line to be as long as the first",
+        ErrorKind::TooLarge => "a range that fits in usize",
+        kind => return format!("to match {}", kind.description()),
+    };
+    description.to_string()
+}
 
 pub fn parse_all_to<'a, T>(
     input: &'a str,
     parser: impl Parser<&'a str, T, nom::error::Error<&'a str>>,
 ) -> Result<T> {
     let (_, res) = all_consuming(terminated(parser, opt(line_ending)))(input)
-        .map_err(|e| e.to_owned())
-        .context("failed to parse input")?;
+        .map_err(|e| ParseError::from_nom(input, e))?;
     Ok(res)
 }
 
 pub fn parse_lines_to_vec<'a, T>(
     input: &'a str,
-    parser: impl Parser<&'a str, T, nom::error::Error<&'a str>>,
+    mut parser: impl Parser<&'a str, T, nom::error::Error<&'a str>>,
 ) -> Result<Vec<T>> {
-    let (_, res) = all_consuming(terminated(
-        separated_list1(line_ending, parser),
+    let (rest, res) = terminated(
+        separated_list1(line_ending, |i| parser.parse(i)),
         opt(line_ending),
-    ))(input)
-    .map_err(|e| e.to_owned())
-    .context("failed to parse input")?;
+    )(input)
+    .map_err(|e| ParseError::from_nom(input, e))?;
+    if !rest.is_empty() {
+        let offset = input.len() - rest.len();
+        if !input[..offset].ends_with('\n') {
+            // A line was parsed without reaching its end
+            return Err(ParseError::at_offset(input, offset, "a line ending").into());
+        }
+        // The list ends at the first line that can't be parsed, so parse it
+        // again to find out why
+        let error = match parser.parse(rest) {
+            Err(e) => ParseError::from_nom(input, e),
+            Ok((after, _)) => {
+                ParseError::at_offset(input, input.len() - after.len(), "a line ending")
+            }
+        };
+        return Err(error.into());
+    }
     Ok(res)
 }
 
 pub fn number(digits: &str) -> IResult<&str, usize> {
-    map_res(digit1, |n: &str| n.parse::<usize>())(digits)
+    unsigned(digits)
+}
+
+/// Parses a number without a sign into any integer type
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parses a number that may start with `-` or `+` into any integer type
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Parses one or more `item`s separated by spaces or tabs on the same line
+pub fn spaced<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(space1, item)
+}
+
+/// Parses an empty line between two sections of the input, along with the
+/// end of the line before it
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((line_ending, space0, line_ending)))(input)
+}
+
+/// Parses one or more `section`s separated by blank lines
+pub fn sections<'a, O, F>(section: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(blank_line, section)
+}
+
+/// Parses a `key: value` line with the given `key`, giving just the value.
+/// Any amount of space (including none) can follow the colon.
+pub fn labelled<'a, O, F>(key: &'a str, value: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    preceded(tuple((tag(key), char(':'), space0)), value)
+}
+
+/// Parses lines of characters into a grid, where `cell` reads each character
+/// or rejects it with `None`. The grid ends at a blank line or the end of the
+/// input, and every line must be as long as the first. Unlike most parsers,
+/// a rejected character or a line of the wrong length fails straight away,
+/// so the error points at it rather than at the start of the grid.
+pub fn char_grid<'a, T, F>(mut cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    move |input| {
+        let fail = |at: &'a str, kind| nom::Err::Failure(nom::error::Error::new(at, kind));
+        let mut rows: Vec<Vec<T>> = vec![];
+        let mut rest = input;
+        loop {
+            let mut row = vec![];
+            let mut end = rest.len();
+            for (i, c) in rest.char_indices() {
+                if c == '\r' || c == '\n' {
+                    end = i;
+                    break;
+                }
+                match cell(c) {
+                    Some(value) => row.push(value),
+                    None => return Err(fail(&rest[i..], ErrorKind::MapOpt)),
+                }
+            }
+            if row.is_empty() {
+                let error = nom::error::Error::new(rest, ErrorKind::Many1);
+                return Err(nom::Err::Error(error));
+            }
+            let width = rows.first().map_or(row.len(), Vec::len);
+            if row.len() != width {
+                // Point to where the line should have ended
+                let at = rest.char_indices().nth(width).map_or(end, |(i, _)| i);
+                return Err(fail(&rest[at.min(end)..], ErrorKind::LengthValue));
+            }
+            rows.push(row);
+            rest = &rest[end..];
+
+            // Carry on if there's another line with something on it
+            match line_ending::<_, nom::error::Error<&str>>(rest) {
+                Ok((next, _)) if !next.is_empty() && !next.starts_with(['\r', '\n']) => rest = next,
+                _ => break,
+            }
+        }
+        let grid = Grid::from_rows(rows).map_err(|_| fail(input, ErrorKind::LengthValue))?;
+        Ok((rest, grid))
+    }
+}
+
+/// Parses a colour code like `#70c710` into its value
+pub fn hex_colour(input: &str) -> IResult<&str, u32> {
+    let hex_digits = take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit());
+    map_res(preceded(char('#'), hex_digits), |hex| {
+        u32::from_str_radix(hex, 16)
+    })(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::{bytes::complete::tag, sequence::preceded};
+
+    fn parse_error(res: Result<Vec<usize>>) -> ParseError {
+        res.unwrap_err().downcast::<ParseError>().unwrap()
+    }
+
+    #[test]
+    fn test_it_finds_the_line_and_column_of_an_error() {
+        let err = ParseError::at_offset("abc\ndef\nghi", 9, "a digit");
+        assert_eq!((err.line, err.column), (3, 2));
+        assert_eq!(err.snippet, "ghi");
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 2: expected a digit near \"ghi\""
+        );
+    }
+
+    #[test]
+    fn test_it_shortens_long_snippets() {
+        let line = (0..100).map(|n| (n % 10).to_string()).collect::<String>();
+        let err = ParseError::at(&line, 0, 50, "a letter");
+        assert_eq!(err.snippet, "01234567890123456789012345678901234567890");
+        let err = ParseError::at(&line, 0, 2, "a letter");
+        assert_eq!(err.snippet, "01234567890123456789012");
+    }
+
+    #[test]
+    fn test_it_reports_the_line_that_failed() {
+        let input = "n 1\nn 2\nn x\nn 4";
+        let err = parse_error(parse_lines_to_vec(input, preceded(tag("n "), number)));
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.snippet, "n x");
+        assert_eq!(err.expected, "a digit");
+
+        let err = parse_error(parse_lines_to_vec("n 1\nm 2", preceded(tag("n "), number)));
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "a keyword or separator");
+    }
+
+    #[test]
+    fn test_it_reports_unparsed_input() {
+        let err = parse_all_to("12\n34", number)
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "the end of the input");
+    }
+
+    #[test]
+    fn test_it_parses_numbers_with_signs() {
+        assert_eq!(signed::<i64>("-12 3"), Ok((" 3", -12)));
+        assert_eq!(signed::<i8>("+7"), Ok(("", 7)));
+        assert!(signed::<i8>("300").is_err());
+        assert!(signed::<i32>("-x").is_err());
+        assert_eq!(unsigned::<u16>("42"), Ok(("", 42)));
+    }
+
+    #[test]
+    fn test_it_parses_labelled_lists_and_sections() {
+        let res = parse_all_to("Time:   7  15\t30", labelled("Time", spaced(number)));
+        assert_eq!(res.unwrap(), vec![7, 15, 30]);
+        assert_eq!(labelled("x", signed::<i32>)("x:-1"), Ok(("", -1)));
+
+        let input = "1 2\r\n3\r\n\r\n4\n  \n5 6\n";
+        let res = parse_all_to(
+            input,
+            sections(separated_list1(line_ending, spaced(number))),
+        );
+        assert_eq!(
+            res.unwrap(),
+            vec![vec![vec![1, 2], vec![3]], vec![vec![4]], vec![vec![5, 6]]]
+        );
+
+        // A list doesn't carry on over the end of the line
+        assert!(parse_all_to("1 2\r\n3", spaced(number)).is_err());
+    }
+
+    #[test]
+    fn test_it_parses_grids_and_colours() {
+        let is_hash = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = parse_all_to("#.\r\n.#\r\n", char_grid(is_hash)).unwrap();
+        assert_eq!(grid.width(), 2);
+        assert!(grid[(1, 1)]);
+        let (rest, _) = char_grid(is_hash)("#.\n.#\n\n##").unwrap();
+        assert_eq!(rest, "\n\n##");
+
+        let error = |input| {
+            parse_all_to(input, char_grid(is_hash))
+                .unwrap_err()
+                .downcast::<ParseError>()
+                .unwrap()
+        };
+        let err = error("#.\n.");
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "every line to be as long as the first");
+        let err = error("#.\n.#.");
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = error("#.\n.x");
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a valid value");
+
+        assert_eq!(hex_colour("#70c710)"), Ok((")", 0x70c710)));
+        assert!(hex_colour("#70c71").is_err());
+    }
 }
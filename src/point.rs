@@ -1,5 +1,10 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
 use num::integer::Integer;
-use num::ToPrimitive;
+use num::{CheckedAdd, CheckedSub, NumCast, One, ToPrimitive};
+
+use crate::direction::Direction8;
 
 pub trait Point<T>
 where
@@ -8,3 +13,399 @@ where
     fn x(&self) -> T;
     fn y(&self) -> T;
 }
+
+/// A point on a plane. When it's on a grid, y increases to the south, like
+/// the grid's coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + Ord + Sub<Output = T>,
+{
+    /// The distance when moving along the rows and columns of a grid
+    pub fn manhattan_distance(&self, other: &Self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance when moving diagonally as well, like a king in chess
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    /// The straight line distance
+    pub fn euclidean_distance(&self, other: &Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let dx = abs_diff(self.x, other.x).to_f64().unwrap_or(f64::NAN);
+        let dy = abs_diff(self.y, other.y).to_f64().unwrap_or(f64::NAN);
+        dx.hypot(dy)
+    }
+
+    /// Is the point in the rectangle from `min` up to but not including
+    /// `max`?
+    pub fn is_within(&self, min: &Self, max: &Self) -> bool {
+        (min.x..max.x).contains(&self.x) && (min.y..max.y).contains(&self.y)
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Copy + CheckedAdd + CheckedSub + One,
+{
+    /// Moves one step in `direction`, unless the coordinates would overflow,
+    /// e.g. by going below 0 for unsigned coordinates
+    pub fn step(&self, direction: impl Into<Direction8>) -> Option<Self> {
+        let (dx, dy) = direction.into().delta();
+        Some(Self::new(add_unit(self.x, dx)?, add_unit(self.y, dy)?))
+    }
+
+    /// Gets the points above, below, left and right of this one
+    pub fn neighbours4(&self) -> impl Iterator<Item = Self> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter(|d| !d.is_diagonal())
+            .filter_map(|d| self.step(d))
+    }
+
+    /// Gets the points around this one, including diagonally
+    pub fn neighbours8(&self) -> impl Iterator<Item = Self> + '_ {
+        Direction8::ALL.into_iter().filter_map(|d| self.step(d))
+    }
+}
+
+impl<T: Copy + ToPrimitive> Point2<T> {
+    /// Converts the coordinates to another type, if they fit in it
+    pub fn cast<U: NumCast>(&self) -> Option<Point2<U>> {
+        Some(Point2::new(U::from(self.x)?, U::from(self.y)?))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + Ord + Sub<Output = T>,
+{
+    pub fn manhattan_distance(&self, other: &Self) -> T
+    where
+        T: Add<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        [
+            abs_diff(self.x, other.x),
+            abs_diff(self.y, other.y),
+            abs_diff(self.z, other.z),
+        ]
+        .into_iter()
+        .map(|d| d.to_f64().unwrap_or(f64::NAN).powi(2))
+        .sum::<f64>()
+        .sqrt()
+    }
+
+    /// Is the point in the box from `min` up to but not including `max`?
+    pub fn is_within(&self, min: &Self, max: &Self) -> bool {
+        (min.x..max.x).contains(&self.x)
+            && (min.y..max.y).contains(&self.y)
+            && (min.z..max.z).contains(&self.z)
+    }
+}
+
+impl<T> Point3<T>
+where
+    T: Copy + CheckedAdd + CheckedSub + One,
+{
+    /// Gets the six points that share a face with this one, leaving out any
+    /// whose coordinates would overflow
+    pub fn neighbours6(&self) -> impl Iterator<Item = Self> + '_ {
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .filter_map(|(dx, dy, dz)| {
+            Some(Self::new(
+                add_unit(self.x, dx)?,
+                add_unit(self.y, dy)?,
+                add_unit(self.z, dz)?,
+            ))
+        })
+    }
+}
+
+impl<T: Copy + ToPrimitive> Point3<T> {
+    /// Converts the coordinates to another type, if they fit in it
+    pub fn cast<U: NumCast>(&self) -> Option<Point3<U>> {
+        Some(Point3::new(
+            U::from(self.x)?,
+            U::from(self.y)?,
+            U::from(self.z)?,
+        ))
+    }
+}
+
+impl<T> Point<T> for Point2<T>
+where
+    T: Copy + Integer + ToPrimitive,
+{
+    fn x(&self) -> T {
+        self.x
+    }
+
+    fn y(&self) -> T {
+        self.y
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(value: Point2<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(value: Point3<T>) -> Self {
+        (value.x, value.y, value.z)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+/// Gets the difference between `a` and `b`, which works for unsigned numbers
+/// too as it never goes below 0
+fn abs_diff<T>(a: T, b: T) -> T
+where
+    T: Ord + Sub<Output = T>,
+{
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Adds a `delta` of -1, 0 or 1 to `n`, unless it would overflow
+fn add_unit<T>(n: T, delta: isize) -> Option<T>
+where
+    T: Copy + CheckedAdd + CheckedSub + One,
+{
+    match delta.signum() {
+        1 => n.checked_add(&T::one()),
+        -1 => n.checked_sub(&T::one()),
+        _ => Some(n),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_it_does_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(a * 3, Point2::new(3, 6));
+        assert_eq!(-a, Point2::new(-1, -2));
+
+        let mut c = Point3::new(1, 2, 3);
+        c += Point3::new(1, 1, 1);
+        assert_eq!(c, Point3::new(2, 3, 4));
+        c -= Point3::new(2, 2, 2) * 2;
+        assert_eq!(c, Point3::new(-2, -1, 0));
+    }
+
+    #[test]
+    fn test_it_measures_distances() {
+        let a = Point2::new(1usize, 5);
+        let b = Point2::new(4usize, 1);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(b.manhattan_distance(&a), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.euclidean_distance(&b), 5.0);
+
+        let a = Point3::new(0, 0, 0);
+        let b = Point3::new(-2, 3, 6);
+        assert_eq!(a.manhattan_distance(&b), 11);
+        assert_eq!(a.chebyshev_distance(&b), 6);
+        assert_eq!(a.euclidean_distance(&b), 7.0);
+    }
+
+    #[test]
+    fn test_neighbours_stay_within_the_type() {
+        let corner = Point2::new(0usize, 0);
+        assert_eq!(
+            corner.neighbours4().collect::<Vec<_>>(),
+            vec![Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(corner.neighbours8().count(), 3);
+        assert_eq!(Point2::new(1, 1).neighbours8().count(), 8);
+        assert_eq!(Point3::new(0u8, 0, 255).neighbours6().count(), 3);
+
+        let min = Point2::new(0, 0);
+        let max = Point2::new(3, 3);
+        assert!(Point2::new(2, 0).is_within(&min, &max));
+        assert!(!Point2::new(3, 0).is_within(&min, &max));
+        assert!(!Point2::new(-1, 0).is_within(&min, &max));
+        assert_eq!(Point2::new(-1, 0).cast::<usize>(), None);
+        assert_eq!(Point2::new(1, 2).cast::<u8>(), Some(Point2::new(1, 2)));
+    }
+}
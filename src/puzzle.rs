@@ -4,95 +4,347 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::input::Loader;
+use crate::answer::Answer;
+use crate::input::{normalise::normalise, Loader};
+use crate::solver::{registry, Parsed, Solver};
+
+/// The year used for puzzles and ranges of days that are given without one.
+pub const DEFAULT_YEAR: u16 = 2023;
+
+/// The year of the first Advent of Code.
+const FIRST_YEAR: u16 = 2015;
 
 #[derive(Debug, Clone)]
 pub struct Puzzle {
+    year: u16,
     day: u16,
     part: u16,
 }
 
 impl Puzzle {
-    pub fn run<L>(&self, loader: L) -> Result<(Box<dyn Display>, Duration)>
+    pub fn new(year: u16, day: u16, part: u16) -> Self {
+        Self { year, day, part }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u16 {
+        self.day
+    }
+
+    pub fn part(&self) -> u16 {
+        self.part
+    }
+
+    /// Gets the registered solver for this puzzle
+    pub fn solver(&self) -> Result<&'static dyn Solver> {
+        let Some(solver) = registry().get(self.year, self.day, self.part) else {
+            bail!("{} is not implemented", self);
+        };
+        Ok(solver)
+    }
+
+    /// Loads the puzzle's input, normalising it unless the puzzle's solver
+    /// wants it raw
+    pub fn load_input<L>(&self, loader: &L) -> Result<String>
     where
-        L: Loader,
+        L: Loader + ?Sized,
     {
-        let input = loader.load(self.day)?;
-        let start = Instant::now();
-        Ok((self.run_part(input.as_ref())?, start.elapsed()))
-    }
-
-    fn run_part(&self, input: &str) -> Result<Box<dyn Display>> {
-        match (self.day, self.part) {
-            (1, 1) => Ok(Box::new(crate::day1::part1(input)?)),
-            (1, 2) => Ok(Box::new(crate::day1::part2(input)?)),
-            (2, 1) => Ok(Box::new(crate::day2::part1(input)?)),
-            (2, 2) => Ok(Box::new(crate::day2::part2(input)?)),
-            (3, 1) => Ok(Box::new(crate::day3::part1(input)?)),
-            (3, 2) => Ok(Box::new(crate::day3::part2(input)?)),
-            (4, 1) => Ok(Box::new(crate::day4::part1(input)?)),
-            (4, 2) => Ok(Box::new(crate::day4::part2(input)?)),
-            (5, 1) => Ok(Box::new(crate::day5::part1(input)?)),
-            (5, 2) => Ok(Box::new(crate::day5::part2(input)?)),
-            (6, 1) => Ok(Box::new(crate::day6::part1(input)?)),
-            (6, 2) => Ok(Box::new(crate::day6::part2(input)?)),
-            (7, 1) => Ok(Box::new(crate::day7::part1(input)?)),
-            (7, 2) => Ok(Box::new(crate::day7::part2(input)?)),
-            (8, 1) => Ok(Box::new(crate::day8::part1(input)?)),
-            (8, 2) => Ok(Box::new(crate::day8::part2(input)?)),
-            (9, 1) => Ok(Box::new(crate::day9::part1(input)?)),
-            (9, 2) => Ok(Box::new(crate::day9::part2(input)?)),
-            (10, 1) => Ok(Box::new(crate::day10::part1(input)?)),
-            (10, 2) => Ok(Box::new(crate::day10::part2(input)?)),
-            (11, 1) => Ok(Box::new(crate::day11::part1(input)?)),
-            (11, 2) => Ok(Box::new(crate::day11::part2(input)?)),
-            (12, 1) => Ok(Box::new(crate::day12::part1(input)?)),
-            (12, 2) => Ok(Box::new(crate::day12::part2(input)?)),
-            (13, 1) => Ok(Box::new(crate::day13::part1(input)?)),
-            (13, 2) => Ok(Box::new(crate::day13::part2(input)?)),
-            (14, 1) => Ok(Box::new(crate::day14::part1(input)?)),
-            (14, 2) => Ok(Box::new(crate::day14::part2(input)?)),
-            (15, 1) => Ok(Box::new(crate::day15::part1(input)?)),
-            (15, 2) => Ok(Box::new(crate::day15::part2(input)?)),
-            (16, 1) => Ok(Box::new(crate::day16::part1(input)?)),
-            (16, 2) => Ok(Box::new(crate::day16::part2(input)?)),
-            (17, 1) => Ok(Box::new(crate::day17::part1(input)?)),
-            (17, 2) => Ok(Box::new(crate::day17::part2(input)?)),
-            (18, 1) => Ok(Box::new(crate::day18::part1(input)?)),
-            (18, 2) => Ok(Box::new(crate::day18::part2(input)?)),
-            _ => bail!("day {} part {} is not implemented", self.day, self.part),
+        let input = loader.load(self.year, self.day)?;
+        if self.solver()?.normalises_input() {
+            Ok(normalise(&input))
+        } else {
+            Ok(input)
         }
     }
+
+    pub fn run<L>(&self, loader: &L) -> Result<(Answer, Timings)>
+    where
+        L: Loader + ?Sized,
+    {
+        self.run_sharing(loader, &mut None)
+    }
+
+    /// Runs the puzzle using the parsed input in `shared` if it was parsed for
+    /// the same year and day, and is accepted by this puzzle's solver.
+    /// Otherwise the input is loaded and parsed, and `shared` is replaced
+    /// with it.
+    fn run_sharing<L>(
+        &self,
+        loader: &L,
+        shared: &mut Option<((u16, u16), Parsed)>,
+    ) -> Result<(Answer, Timings)>
+    where
+        L: Loader + ?Sized,
+    {
+        let solver = self.solver()?;
+        let (parsed, parse) = match shared.take() {
+            Some((key, parsed)) if key == (self.year, self.day) && solver.accepts(&parsed) => {
+                (parsed, None)
+            }
+            _ => {
+                let input = self.load_input(loader)?;
+                let start = Instant::now();
+                let parsed = solver.parse(input.as_ref())?;
+                (parsed, Some(start.elapsed()))
+            }
+        };
+        let parsed = &shared.insert(((self.year, self.day), parsed)).1;
+
+        let start = Instant::now();
+        let answer = solver.solve(parsed)?;
+        let solve = start.elapsed();
+        Ok((answer, Timings { parse, solve }))
+    }
+}
+
+/// How long each phase of running a puzzle took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    /// `None` if the puzzle reused input that was parsed for another part
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+/// Runs each of `puzzles` in order. Consecutive puzzles for the same day share
+/// their input, so it's only loaded and parsed once.
+pub fn run_all<L>(puzzles: &[Puzzle], loader: &L) -> Vec<Result<(Answer, Timings)>>
+where
+    L: Loader + ?Sized,
+{
+    let mut shared = None;
+    puzzles
+        .iter()
+        .map(|puzzle| puzzle.run_sharing(loader, &mut shared))
+        .collect()
 }
 
 impl Display for Puzzle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "day {} part {}", self.day, self.part)
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)
     }
 }
 
+/// Accepts "{day}-{part}" for a puzzle from `DEFAULT_YEAR`, or
+/// "{year}-{day}-{part}".
 impl TryFrom<&str> for Puzzle {
     type Error = String;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let parts = s.split_once('-');
-        if let Some((day, part)) = parts {
-            let day = day.parse::<u16>();
-            let part = part.parse::<u16>();
-            if day.is_err() || part.is_err() {
-                return Err("Day and part must be integers".into());
-            }
-            let day = day.unwrap();
-            if !(1..=25).contains(&day) {
-                return Err("Day must be between 1 and 25".into());
+        let (year, day, part) = match s.split('-').collect::<Vec<_>>()[..] {
+            [day, part] => (Ok(DEFAULT_YEAR), day, part),
+            [year, day, part] => (parse_year(year), day, part),
+            _ => {
+                return Err(
+                    "puzzle must be given in the format {day}-{part} or {year}-{day}-{part}"
+                        .to_string(),
+                )
             }
-            let part = part.unwrap();
-            if !(1..=2).contains(&part) {
-                return Err("Part must be either 1 or 2".into());
-            }
-            Ok(Self { day, part })
+        };
+        let year = year?;
+        let day = day.parse::<u16>();
+        let part = part.parse::<u16>();
+        if day.is_err() || part.is_err() {
+            return Err("Day and part must be integers".into());
+        }
+        let day = day.unwrap();
+        if !(1..=25).contains(&day) {
+            return Err("Day must be between 1 and 25".into());
+        }
+        let part = part.unwrap();
+        if !(1..=2).contains(&part) {
+            return Err("Part must be either 1 or 2".into());
+        }
+        Ok(Self { year, day, part })
+    }
+}
+
+fn parse_year(s: &str) -> Result<u16, String> {
+    match s.parse::<u16>() {
+        Ok(year) if year >= FIRST_YEAR => Ok(year),
+        _ => Err(format!("Year must be {} or later", FIRST_YEAR)),
+    }
+}
+
+/// Does `s` look like a year rather than a day?
+fn is_year(s: &str) -> bool {
+    s.len() == 4 && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// An inclusive range of days in one year, used to select several puzzles at
+/// once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    year: u16,
+    first: u16,
+    last: u16,
+}
+
+impl DayRange {
+    pub fn contains(&self, year: u16, day: u16) -> bool {
+        year == self.year && (self.first..=self.last).contains(&day)
+    }
+
+    /// Gets every implemented puzzle in the range, ordered by day and then part.
+    pub fn puzzles(&self) -> Vec<Puzzle> {
+        registry()
+            .iter()
+            .filter(|s| self.contains(s.year(), s.day()))
+            .map(|s| Puzzle::new(s.year(), s.day(), s.part()))
+            .collect()
+    }
+}
+
+impl Display for DayRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.first == self.last {
+            write!(f, "{} day {}", self.year, self.first)
         } else {
-            Err("puzzle must be given in the format {day}-{part}".to_string())
+            write!(f, "{} days {}-{}", self.year, self.first, self.last)
+        }
+    }
+}
+
+/// Accepts the days on their own for a range in `DEFAULT_YEAR`, or prefixed
+/// with a year, e.g. "2023-5-*". A year on its own selects all of its days.
+impl TryFrom<&str> for DayRange {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (year, s) = match s.split_once('-') {
+            Some((year, days)) if is_year(year) => (parse_year(year)?, days),
+            _ if is_year(s) => (parse_year(s)?, "all"),
+            _ => (DEFAULT_YEAR, s),
+        };
+        if s == "all" {
+            return Ok(Self {
+                year,
+                first: 1,
+                last: 25,
+            });
+        }
+        let (first, last) = s.split_once('-').unwrap_or((s, s));
+        let Ok(first) = first.parse::<u16>() else {
+            return Err("First day must be an integer".into());
+        };
+        let last = if last == "*" {
+            25
+        } else {
+            let Ok(last) = last.parse::<u16>() else {
+                return Err("Last day must be an integer or '*'".into());
+            };
+            last
+        };
+        if !(1..=25).contains(&first) || !(1..=25).contains(&last) {
+            return Err("Days must be between 1 and 25".into());
+        }
+        if first > last {
+            return Err("First day must not be after the last day".into());
+        }
+        Ok(Self { year, first, last })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::string_loader::StringLoader;
+
+    #[test]
+    fn test_run_all_parses_each_day_once() {
+        let loader = StringLoader::new("1abc2\npqr3stu8vwx");
+        let puzzles = vec![
+            Puzzle::new(2023, 1, 1),
+            Puzzle::new(2023, 1, 2),
+            Puzzle::new(2023, 2, 1),
+        ];
+        let results = run_all(&puzzles, &loader);
+
+        let (answer, timings) = results[0].as_ref().unwrap();
+        assert_eq!(answer.to_string(), "50");
+        assert!(timings.parse.is_some());
+        let (answer, timings) = results[1].as_ref().unwrap();
+        assert_eq!(answer.to_string(), "50");
+        assert!(timings.parse.is_none());
+        // day 2 can't parse day 1's input
+        assert!(results[2].is_err());
+    }
+
+    #[test]
+    fn test_every_day_accepts_input_saved_on_windows() {
+        for example in registry().examples() {
+            let input = format!("\u{feff}{}\r\n\r\n", example.input.replace('\n', "\r\n"));
+            let puzzle = Puzzle::new(example.year, example.day, example.part);
+            let (answer, _) = puzzle.run(&StringLoader::new(input)).unwrap();
+            assert_eq!(answer, example.expected, "wrong answer for {}", puzzle);
+        }
+    }
+
+    #[test]
+    fn test_can_parse_day_ranges() {
+        let range = |year, first, last| DayRange { year, first, last };
+        let cases = vec![
+            ("all", range(2023, 1, 25)),
+            ("7", range(2023, 7, 7)),
+            ("1-18", range(2023, 1, 18)),
+            ("5-*", range(2023, 5, 25)),
+            ("2022", range(2022, 1, 25)),
+            ("2022-all", range(2022, 1, 25)),
+            ("2022-7", range(2022, 7, 7)),
+            ("2015-1-18", range(2015, 1, 18)),
+            ("2016-5-*", range(2016, 5, 25)),
+        ];
+        for (input, expect) in cases {
+            assert_eq!(DayRange::try_from(input), Ok(expect));
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_day_ranges() {
+        for input in [
+            "", "0", "26", "5-3", "*-5", "1-x", "1-26", "2014", "2023-26",
+        ] {
+            assert!(
+                DayRange::try_from(input).is_err(),
+                "expected '{}' to be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_day_range_only_selects_implemented_puzzles() {
+        let range = DayRange::try_from("17-*").unwrap();
+        let puzzles = range
+            .puzzles()
+            .iter()
+            .map(|p| (p.day(), p.part()))
+            .collect::<Vec<_>>();
+        assert_eq!(puzzles, vec![(17, 1), (17, 2), (18, 1), (18, 2)]);
+        assert!(DayRange::try_from("2022").unwrap().puzzles().is_empty());
+        assert_eq!(range.to_string(), "2023 days 17-25");
+        assert_eq!(DayRange::try_from("7").unwrap().to_string(), "2023 day 7");
+    }
+
+    #[test]
+    fn test_can_parse_puzzles() {
+        let cases = vec![
+            ("5-2", (2023, 5, 2)),
+            ("2023-5-2", (2023, 5, 2)),
+            ("2019-25-1", (2019, 25, 1)),
+        ];
+        for (input, expect) in cases {
+            let puzzle = Puzzle::try_from(input).unwrap();
+            assert_eq!((puzzle.year(), puzzle.day(), puzzle.part()), expect);
+        }
+        for input in ["5", "5-3", "0-1", "2014-5-2", "23-5-2", "2023-5-2-1"] {
+            assert!(
+                Puzzle::try_from(input).is_err(),
+                "expected '{}' to be rejected",
+                input
+            );
         }
     }
 }
@@ -0,0 +1,363 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::{io::Write, time::Duration};
+
+use crate::answer::Answer;
+
+use crate::answers::{Answers, Verdict};
+use crate::puzzle::{Puzzle, Timings};
+
+/// The result of running a single puzzle.
+pub struct Outcome {
+    pub puzzle: Puzzle,
+    pub result: Result<(Answer, Timings)>,
+    /// Only set when the answer was checked against the stored answers
+    pub verdict: Option<Verdict>,
+}
+
+/// How the outcomes of running puzzles are written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Format {
+    /// A table for reading in a terminal
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+/// One outcome flattened into the fields written by the machine-readable
+/// formats. Durations are in nanoseconds, and `parse_ns` is empty when the
+/// parsed input was shared with the previous puzzle.
+#[derive(Debug, Serialize)]
+struct Record {
+    year: u16,
+    day: u16,
+    part: u16,
+    answer: Option<Answer>,
+    answer_type: Option<&'static str>,
+    parse_ns: Option<u128>,
+    solve_ns: Option<u128>,
+    check: Option<String>,
+    error: Option<String>,
+}
+
+const RECORD_FIELDS: [&str; 9] = [
+    "year",
+    "day",
+    "part",
+    "answer",
+    "answer_type",
+    "parse_ns",
+    "solve_ns",
+    "check",
+    "error",
+];
+
+impl Outcome {
+    pub fn new(puzzle: Puzzle, result: Result<(Answer, Timings)>) -> Self {
+        Self {
+            puzzle,
+            result,
+            verdict: None,
+        }
+    }
+
+    fn record(&self) -> Record {
+        let (answer, parse_ns, solve_ns, error) = match &self.result {
+            Ok((answer, timings)) => (
+                Some(answer.clone()),
+                timings.parse.map(|p| p.as_nanos()),
+                Some(timings.solve.as_nanos()),
+                None,
+            ),
+            Err(e) => (None, None, None, Some(format!("{:#}", e))),
+        };
+        Record {
+            year: self.puzzle.year(),
+            day: self.puzzle.day(),
+            part: self.puzzle.part(),
+            answer,
+            answer_type: self.result.as_ref().ok().map(|(a, _)| a.kind()),
+            parse_ns,
+            solve_ns,
+            check: self.verdict.as_ref().map(|v| v.to_string()),
+            error,
+        }
+    }
+
+    /// Compares the puzzle's answer with the one in `answers`. Puzzles that
+    /// returned an error are left without a verdict.
+    pub fn check(&mut self, answers: &Answers) {
+        if let Ok((answer, _)) = &self.result {
+            self.verdict = Some(answers.check(
+                self.puzzle.year(),
+                self.puzzle.day(),
+                self.puzzle.part(),
+                answer,
+            ));
+        }
+    }
+
+    /// Did the puzzle return an error or an answer that doesn't match the
+    /// stored one?
+    pub fn is_failure(&self) -> bool {
+        self.result.is_err() || self.verdict.as_ref().is_some_and(Verdict::is_fail)
+    }
+}
+
+/// Writes `outcomes` to `out` in the given format.
+pub fn write_outcomes<W>(out: &mut W, outcomes: &[Outcome], format: Format) -> Result<()>
+where
+    W: Write,
+{
+    match format {
+        Format::Text => write_table(out, outcomes)?,
+        Format::Json => {
+            let records = outcomes.iter().map(Outcome::record).collect::<Vec<_>>();
+            serde_json::to_writer_pretty(&mut *out, &records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => write_delimited(out, outcomes, ',')?,
+        Format::Tsv => write_delimited(out, outcomes, '\t')?,
+    }
+    Ok(())
+}
+
+/// Writes a header line and then one line per outcome, with the fields
+/// separated by `delimiter`. Missing values are left empty.
+fn write_delimited<W>(out: &mut W, outcomes: &[Outcome], delimiter: char) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let write_line = |out: &mut W, fields: Vec<String>| {
+        let fields = fields
+            .iter()
+            .map(|f| escape_field(f, delimiter))
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", fields.join(&delimiter.to_string()))
+    };
+
+    write_line(out, RECORD_FIELDS.iter().map(|f| f.to_string()).collect())?;
+    for record in outcomes.iter().map(Outcome::record) {
+        let fields = vec![
+            record.year.to_string(),
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer.map_or(String::new(), |a| a.to_string()),
+            record.answer_type.unwrap_or_default().to_string(),
+            record.parse_ns.map_or(String::new(), |p| p.to_string()),
+            record.solve_ns.map_or(String::new(), |s| s.to_string()),
+            record.check.unwrap_or_default(),
+            record.error.unwrap_or_default(),
+        ];
+        write_line(out, fields)?;
+    }
+    Ok(())
+}
+
+/// CSV fields that contain the delimiter, quotes or line breaks are quoted.
+/// TSV has no quoting, so tabs and line breaks are replaced with spaces.
+fn escape_field(field: &str, delimiter: char) -> String {
+    if delimiter == '\t' {
+        return field.replace(['\t', '\r', '\n'], " ");
+    }
+    if field.contains([delimiter, '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes a table with the answer, parse time and solve time of each puzzle,
+/// followed by the total times for all of the puzzles. If any of the puzzles
+/// were checked, a column with the verdicts is added.
+pub fn write_table<W>(out: &mut W, outcomes: &[Outcome]) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let show_verdicts = outcomes.iter().any(|o| o.verdict.is_some());
+    let mut header = vec!["Answer", "Parse", "Solve"];
+    if show_verdicts {
+        header.push("Check");
+    }
+
+    let mut rows = vec![header.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    for o in outcomes.iter() {
+        let mut row = match &o.result {
+            Ok((answer, timings)) => vec![
+                answer.to_string(),
+                timings
+                    .parse
+                    .map_or(String::from("shared"), |p| format!("{:?}", p)),
+                format!("{:?}", timings.solve),
+            ],
+            Err(e) => vec![
+                format!("error: {}", e),
+                String::from("-"),
+                String::from("-"),
+            ],
+        };
+        if show_verdicts {
+            row.push(o.verdict.as_ref().map_or(String::new(), |v| v.to_string()));
+        }
+        rows.push(row);
+    }
+    let (parse_total, solve_total) = outcomes.iter().filter_map(|o| o.result.as_ref().ok()).fold(
+        (Duration::ZERO, Duration::ZERO),
+        |(parse, solve), (_, t)| (parse + t.parse.unwrap_or_default(), solve + t.solve),
+    );
+    let total = vec![
+        String::new(),
+        format!("{:?}", parse_total),
+        format!("{:?}", solve_total),
+    ];
+
+    let widths = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .chain(std::iter::once(&total))
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let write_row = |out: &mut W, start: String, row: &[String]| {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .fold(start, |line, (cell, w)| format!("{}  {:<w$}", line, cell));
+        writeln!(out, "{}", line.trim_end())
+    };
+
+    write_row(out, String::from("Day  Part"), &rows[0])?;
+    for (o, row) in outcomes.iter().zip(rows.iter().skip(1)) {
+        let start = format!("{:>3}  {:>4}", o.puzzle.day(), o.puzzle.part());
+        write_row(out, start, row)?;
+    }
+    write_row(out, String::from("Total    "), &total)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn timings(parse: Option<u64>, solve: u64) -> Timings {
+        Timings {
+            parse: parse.map(Duration::from_millis),
+            solve: Duration::from_millis(solve),
+        }
+    }
+
+    fn outcomes() -> Vec<Outcome> {
+        vec![
+            Outcome::new(
+                Puzzle::new(2023, 1, 1),
+                Ok((142usize.into(), timings(Some(1), 2))),
+            ),
+            Outcome::new(Puzzle::new(2023, 1, 2), Err(anyhow!("oops"))),
+            Outcome::new(
+                Puzzle::new(2023, 10, 1),
+                Ok((8usize.into(), timings(Some(10), 1))),
+            ),
+            Outcome::new(
+                Puzzle::new(2023, 10, 2),
+                Ok(((-4i32).into(), timings(None, 3))),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_it_writes_a_table_of_outcomes() {
+        let mut out = Vec::new();
+        write_table(&mut out, &outcomes()).unwrap();
+
+        let expect = "Day  Part  Answer       Parse   Solve
+  1     1  142          1ms     2ms
+  1     2  error: oops  -       -
+ 10     1  8            10ms    1ms
+ 10     2  -4           shared  3ms
+Total                   11ms    6ms
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_it_writes_verdicts_for_checked_outcomes() {
+        let answers = Answers::parse("[2023.day1]\npart1 = 142\n[2023.day10]\npart2 = 4").unwrap();
+        let mut outcomes = outcomes();
+        outcomes.iter_mut().for_each(|o| o.check(&answers));
+        let mut out = Vec::new();
+        write_table(&mut out, &outcomes).unwrap();
+
+        let expect = "Day  Part  Answer       Parse   Solve  Check
+  1     1  142          1ms     2ms    PASS
+  1     2  error: oops  -       -
+ 10     1  8            10ms    1ms    UNKNOWN
+ 10     2  -4           shared  3ms    FAIL (expected 4)
+Total                   11ms    6ms
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expect);
+        let failures = outcomes.iter().filter(|o| o.is_failure()).count();
+        assert_eq!(failures, 2);
+    }
+
+    #[test]
+    fn test_it_writes_csv() {
+        let mut outcomes = outcomes();
+        outcomes[1].result = Err(anyhow!("bad \"input\", line 3"));
+        let mut out = Vec::new();
+        write_outcomes(&mut out, &outcomes, Format::Csv).unwrap();
+
+        let expect = "year,day,part,answer,answer_type,parse_ns,solve_ns,check,error
+2023,1,1,142,unsigned,1000000,2000000,,
+2023,1,2,,,,,,\"bad \"\"input\"\", line 3\"
+2023,10,1,8,unsigned,10000000,1000000,,
+2023,10,2,-4,signed,,3000000,,
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_it_writes_tsv() {
+        let mut out = Vec::new();
+        write_outcomes(&mut out, &outcomes()[..2], Format::Tsv).unwrap();
+
+        let expect = "year\tday\tpart\tanswer\tanswer_type\tparse_ns\tsolve_ns\tcheck\terror
+2023\t1\t1\t142\tunsigned\t1000000\t2000000\t\t
+2023\t1\t2\t\t\t\t\t\toops
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_it_writes_json() {
+        let answers = Answers::parse("[2023.day1]\npart1 = 142").unwrap();
+        let mut outcomes = outcomes();
+        outcomes.iter_mut().for_each(|o| o.check(&answers));
+        let mut out = Vec::new();
+        write_outcomes(&mut out, &outcomes, Format::Json).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 4);
+        assert_eq!(
+            json[0],
+            serde_json::json!({
+                "year": 2023,
+                "day": 1,
+                "part": 1,
+                "answer": 142,
+                "answer_type": "unsigned",
+                "parse_ns": 1000000,
+                "solve_ns": 2000000,
+                "check": "PASS",
+                "error": null,
+            })
+        );
+        assert_eq!(json[1]["error"], "oops");
+        assert_eq!(json[3]["parse_ns"], serde_json::Value::Null);
+    }
+}
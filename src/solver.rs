@@ -0,0 +1,359 @@
+use anyhow::{bail, Result};
+use std::{any::Any, borrow::Borrow, sync::OnceLock};
+
+use crate::answer::Answer;
+
+/// Puzzle input that a `Solver` has parsed into its day-specific form.
+pub type Parsed = Box<dyn Any>;
+
+/// A solution for one part of one day's puzzle.
+///
+/// Solving happens in two phases: the input is parsed first, then the parsed
+/// input is solved. Both parts of a day normally share the same parsed form,
+/// so the input only needs parsing once per day.
+pub trait Solver: Send + Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u16;
+    fn part(&self) -> u16;
+    /// The title of the day's puzzle, e.g. "Trebuchet?!"
+    fn name(&self) -> &'static str;
+    /// Should the input be tidied up by `input::normalise::normalise` before
+    /// it's parsed? Solvers whose input has meaningful trailing whitespace
+    /// can opt out and get the input exactly as it was loaded.
+    fn normalises_input(&self) -> bool {
+        true
+    }
+    fn parse(&self, input: &str) -> Result<Parsed>;
+    /// Can `parsed` be passed to `solve`?
+    fn accepts(&self, parsed: &Parsed) -> bool;
+    fn solve(&self, parsed: &Parsed) -> Result<Answer>;
+}
+
+/// A `Solver` backed by a day's `parse` function and one of its `partN`
+/// functions. The `partN` function can take anything the parsed input can be
+/// borrowed as, e.g. a slice when `parse` returns a `Vec`.
+pub struct FnSolver<P, S, T>
+where
+    S: ?Sized,
+{
+    year: u16,
+    day: u16,
+    part: u16,
+    name: &'static str,
+    parse: fn(&str) -> Result<P>,
+    solve: fn(&S) -> Result<T>,
+    normalises_input: bool,
+}
+
+impl<P, S, T> FnSolver<P, S, T>
+where
+    P: Borrow<S>,
+    S: ?Sized,
+{
+    pub fn new(
+        year: u16,
+        day: u16,
+        part: u16,
+        name: &'static str,
+        parse: fn(&str) -> Result<P>,
+        solve: fn(&S) -> Result<T>,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            name,
+            parse,
+            solve,
+            normalises_input: true,
+        }
+    }
+
+    /// Gives the solver its input exactly as it was loaded, without
+    /// normalising it
+    pub fn with_raw_input(mut self) -> Self {
+        self.normalises_input = false;
+        self
+    }
+}
+
+impl<P, S, T> Solver for FnSolver<P, S, T>
+where
+    P: Borrow<S> + 'static,
+    S: ?Sized,
+    T: Into<Answer>,
+{
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u16 {
+        self.day
+    }
+
+    fn part(&self) -> u16 {
+        self.part
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn normalises_input(&self) -> bool {
+        self.normalises_input
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed> {
+        Ok(Box::new((self.parse)(input)?))
+    }
+
+    fn accepts(&self, parsed: &Parsed) -> bool {
+        parsed.is::<P>()
+    }
+
+    fn solve(&self, parsed: &Parsed) -> Result<Answer> {
+        let Some(parsed) = parsed.downcast_ref::<P>() else {
+            bail!(
+                "{} day {} part {} was given input parsed by another solver",
+                self.year,
+                self.day,
+                self.part
+            );
+        };
+        Ok((self.solve)(parsed.borrow())?.into())
+    }
+}
+
+/// A puzzle's example input, along with the answer it should give.
+pub struct Example {
+    pub year: u16,
+    pub day: u16,
+    pub part: u16,
+    pub input: &'static str,
+    pub expected: Answer,
+}
+
+impl Example {
+    pub fn new<T>(year: u16, day: u16, part: u16, input: &'static str, expected: T) -> Self
+    where
+        T: Into<Answer>,
+    {
+        Self {
+            year,
+            day,
+            part,
+            input,
+            expected: expected.into(),
+        }
+    }
+}
+
+/// All of the known solvers and examples, ordered by year, day and then part.
+#[derive(Default)]
+pub struct Registry {
+    solvers: Vec<Box<dyn Solver>>,
+    examples: Vec<Example>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `solver` to the registry.
+    ///
+    /// Panics if a solver for the same puzzle was already registered.
+    pub fn register<S>(&mut self, solver: S)
+    where
+        S: Solver + 'static,
+    {
+        let key = (solver.year(), solver.day(), solver.part());
+        match self
+            .solvers
+            .binary_search_by_key(&key, |s| (s.year(), s.day(), s.part()))
+        {
+            Ok(_) => panic!("{} day {} part {} is registered twice", key.0, key.1, key.2),
+            Err(idx) => self.solvers.insert(idx, Box::new(solver)),
+        }
+    }
+
+    pub fn get(&self, year: u16, day: u16, part: u16) -> Option<&dyn Solver> {
+        self.solvers
+            .binary_search_by_key(&(year, day, part), |s| (s.year(), s.day(), s.part()))
+            .ok()
+            .map(|idx| self.solvers[idx].as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|s| s.as_ref())
+    }
+
+    /// Adds `example` to the registry.
+    ///
+    /// Panics if an example for the same puzzle was already registered.
+    pub fn register_example(&mut self, example: Example) {
+        let key = (example.year, example.day, example.part);
+        match self
+            .examples
+            .binary_search_by_key(&key, |e| (e.year, e.day, e.part))
+        {
+            Ok(_) => panic!("{} day {} part {} has two examples", key.0, key.1, key.2),
+            Err(idx) => self.examples.insert(idx, example),
+        }
+    }
+
+    pub fn example(&self, year: u16, day: u16, part: u16) -> Option<&Example> {
+        self.examples
+            .binary_search_by_key(&(year, day, part), |e| (e.year, e.day, e.part))
+            .ok()
+            .map(|idx| &self.examples[idx])
+    }
+
+    pub fn examples(&self) -> impl Iterator<Item = &Example> {
+        self.examples.iter()
+    }
+}
+
+/// Gets the registry containing every implemented day's solvers.
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry = Registry::new();
+        crate::day1::register(&mut registry);
+        crate::day2::register(&mut registry);
+        crate::day3::register(&mut registry);
+        crate::day4::register(&mut registry);
+        crate::day5::register(&mut registry);
+        crate::day6::register(&mut registry);
+        crate::day7::register(&mut registry);
+        crate::day8::register(&mut registry);
+        crate::day9::register(&mut registry);
+        crate::day10::register(&mut registry);
+        crate::day11::register(&mut registry);
+        crate::day12::register(&mut registry);
+        crate::day13::register(&mut registry);
+        crate::day14::register(&mut registry);
+        crate::day15::register(&mut registry);
+        crate::day16::register(&mut registry);
+        crate::day17::register(&mut registry);
+        crate::day18::register(&mut registry);
+        registry
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_len(input: &str) -> Result<usize> {
+        Ok(input.len())
+    }
+
+    #[test]
+    fn test_registry_is_ordered_by_year_day_and_part() {
+        let mut registry = Registry::new();
+        registry.register(FnSolver::new(2023, 2, 1, "b", parse_len, |_| Ok(3)));
+        registry.register(FnSolver::new(2022, 5, 1, "c", parse_len, |_| Ok(4)));
+        registry.register(FnSolver::new(2023, 1, 2, "a", parse_len, |_| Ok(2)));
+        registry.register(FnSolver::new(2023, 1, 1, "a", parse_len, |_| Ok(1)));
+
+        let keys = registry
+            .iter()
+            .map(|s| (s.year(), s.day(), s.part()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![(2022, 5, 1), (2023, 1, 1), (2023, 1, 2), (2023, 2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_registry_can_get_a_solver() {
+        let mut registry = Registry::new();
+        registry.register(FnSolver::new(2023, 1, 1, "a", parse_len, |len| Ok(*len)));
+
+        let solver = registry.get(2023, 1, 1).unwrap();
+        let parsed = solver.parse("abc").unwrap();
+        assert_eq!(solver.solve(&parsed).unwrap().to_string(), "3");
+        assert!(registry.get(2023, 1, 2).is_none());
+    }
+
+    #[test]
+    fn test_solver_rejects_input_parsed_by_another_solver() {
+        let by_len = FnSolver::new(2023, 1, 1, "a", parse_len, |len| Ok(*len));
+        let by_str = FnSolver::new(
+            2023,
+            1,
+            2,
+            "a",
+            |s| Ok(s.to_string()),
+            |s: &String| Ok(s.clone()),
+        );
+
+        let parsed = by_str.parse("abc").unwrap();
+        assert!(!by_len.accepts(&parsed));
+        assert!(by_len.solve(&parsed).is_err());
+        assert!(by_str.accepts(&parsed));
+        assert_eq!(by_str.solve(&parsed).unwrap().to_string(), "abc");
+    }
+
+    #[test]
+    fn test_solvers_normalise_their_input_unless_they_opt_out() {
+        let solver = FnSolver::new(2023, 1, 1, "a", parse_len, |len| Ok(*len));
+        assert!(solver.normalises_input());
+        assert!(!solver.with_raw_input().normalises_input());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_registry_rejects_duplicate_solvers() {
+        let mut registry = Registry::new();
+        registry.register(FnSolver::new(2023, 1, 1, "a", parse_len, |_| Ok(1)));
+        registry.register(FnSolver::new(2023, 1, 1, "a", parse_len, |_| Ok(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_registry_rejects_duplicate_examples() {
+        let mut registry = Registry::new();
+        registry.register_example(Example::new(2023, 1, 1, "abc", 3));
+        registry.register_example(Example::new(2023, 1, 1, "abcd", 4));
+    }
+
+    #[test]
+    fn test_every_implemented_day_has_both_parts() {
+        for day in 1..=18 {
+            for part in 1..=2 {
+                assert!(
+                    registry().get(2023, day, part).is_some(),
+                    "day {} part {} is not registered",
+                    day,
+                    part
+                );
+                assert!(
+                    registry().example(2023, day, part).is_some(),
+                    "day {} part {} has no example",
+                    day,
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_example_gives_its_expected_answer() {
+        for example in registry().examples() {
+            let solver = registry()
+                .get(example.year, example.day, example.part)
+                .unwrap();
+            let parsed = solver.parse(example.input).unwrap();
+            assert_eq!(
+                solver.solve(&parsed).unwrap(),
+                example.expected,
+                "wrong answer for the day {} part {} example",
+                example.day,
+                example.part
+            );
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_advent-of-code-2023"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_a_range_without_puzzles_is_an_error() {
+    for (args, range) in [
+        (vec!["run", "2022"], "2022 days 1-25"),
+        (vec!["run", "19-*"], "2023 days 19-25"),
+        (vec!["bench", "--days", "25"], "2023 day 25"),
+    ] {
+        let output = run(&args);
+        assert!(!output.status.success(), "{:?} should fail", args);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        let expected = format!("no puzzles are implemented for {}", range);
+        assert!(stderr.contains(&expected), "{:?} gave {}", args, stderr);
+    }
+}
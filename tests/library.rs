@@ -0,0 +1,27 @@
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::input::string_loader::StringLoader;
+use advent_of_code_2023::puzzle::Puzzle;
+use advent_of_code_2023::{bench, day1, solver};
+
+#[test]
+fn test_a_day_can_be_solved_directly() {
+    let lines = day1::parse("1abc2\npqr3stu8vwx").unwrap();
+    assert_eq!(day1::part1(&lines).unwrap(), 50);
+}
+
+#[test]
+fn test_a_puzzle_can_be_run_from_the_registry() {
+    let example = solver::registry().example(2023, 1, 1).unwrap();
+    let loader = StringLoader::new(example.input);
+    let (answer, _) = Puzzle::new(2023, 1, 1).run(&loader).unwrap();
+    assert_eq!(answer, Answer::from(142));
+}
+
+#[test]
+fn test_a_benchmark_needs_a_timed_run() {
+    let example = solver::registry().example(2023, 1, 1).unwrap();
+    let loader = StringLoader::new(example.input);
+    let puzzle = Puzzle::new(2023, 1, 1);
+    assert!(bench::bench(&puzzle, &loader, 1, 0).is_err());
+    assert_eq!(bench::bench(&puzzle, &loader, 0, 2).unwrap().runs, 2);
+}